@@ -0,0 +1,145 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+
+use crate::error::VibeError;
+use crate::{current_caller, State, STATE};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, CandidType, Deserialize)]
+pub enum Role {
+    Admin,
+    Moderator,
+    Curator,
+}
+
+#[derive(Default, Clone, CandidType, Deserialize)]
+pub struct InitArgs {
+    pub admins: Vec<Principal>,
+}
+
+// Canister controllers are always treated as admins so a deployment can
+// never lock itself out of the role registry
+fn is_controller(principal: &Principal) -> bool {
+    #[cfg(not(test))]
+    {
+        ic_cdk::api::is_controller(principal)
+    }
+    #[cfg(test)]
+    {
+        let _ = principal;
+        false
+    }
+}
+
+// Admins implicitly hold every other role
+pub(crate) fn has_role(state: &State, principal: &Principal, role: Role) -> bool {
+    if is_controller(principal) {
+        return true;
+    }
+
+    state.roles
+        .get(principal)
+        .map(|roles| roles.contains(&Role::Admin) || roles.contains(&role))
+        .unwrap_or(false)
+}
+
+pub(crate) fn require_role(state: &State, principal: &Principal, role: Role) -> Result<(), VibeError> {
+    if has_role(state, principal, role) {
+        Ok(())
+    } else {
+        Err(VibeError::Unauthorized { required: role })
+    }
+}
+
+// Guard helper for admin-only endpoints, checked against the current caller
+pub(crate) fn require_admin(state: &State) -> Result<(), VibeError> {
+    require_role(state, &current_caller(), Role::Admin)
+}
+
+fn admin_count(state: &State) -> usize {
+    state.roles
+        .values()
+        .filter(|roles| roles.contains(&Role::Admin))
+        .count()
+}
+
+pub(crate) fn bootstrap_admins(state: &mut State, admins: &[Principal]) {
+    for admin in admins {
+        if *admin == Principal::anonymous() {
+            continue;
+        }
+        state.roles.entry(*admin).or_default().insert(Role::Admin);
+    }
+}
+
+#[update]
+pub(crate) fn grant_role(principal: Principal, role: Role) -> Result<(), VibeError> {
+    if principal == Principal::anonymous() {
+        return Err(VibeError::AnonymousCaller);
+    }
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        require_admin(&state)?;
+
+        state.roles.entry(principal).or_default().insert(role);
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn revoke_role(principal: Principal, role: Role) -> Result<(), VibeError> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        require_admin(&state)?;
+
+        let is_admin = state.roles
+            .get(&principal)
+            .map(|roles| roles.contains(&Role::Admin))
+            .unwrap_or(false);
+        if role == Role::Admin && is_admin && admin_count(&state) == 1 {
+            return Err(VibeError::LastAdmin);
+        }
+
+        if let Some(roles) = state.roles.get_mut(&principal) {
+            roles.remove(&role);
+            if roles.is_empty() {
+                state.roles.remove(&principal);
+            }
+        }
+        Ok(())
+    })
+}
+
+#[query]
+pub(crate) fn get_roles(principal: Principal) -> Vec<Role> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        let mut roles: Vec<Role> = state.roles
+            .get(&principal)
+            .map(|roles| roles.iter().copied().collect())
+            .unwrap_or_default();
+        roles.sort();
+        roles
+    })
+}
+
+#[query]
+pub(crate) fn get_my_roles() -> Vec<Role> {
+    get_roles(current_caller())
+}
+
+#[query]
+pub(crate) fn list_role_holders(role: Role) -> Result<Vec<Principal>, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        require_admin(&state)?;
+
+        let mut holders: Vec<Principal> = state.roles
+            .iter()
+            .filter(|(_, roles)| roles.contains(&role))
+            .map(|(p, _)| *p)
+            .collect();
+        holders.sort();
+        Ok(holders)
+    })
+}
@@ -0,0 +1,388 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use crate::error::VibeError;
+use crate::marketplace::{self, Sale};
+use crate::notifications::{self, NotificationKind};
+use crate::{
+    current_caller, find_vibe, get_timestamp, is_vibe_visible, rebuild_leaderboard, State, INITIAL_BALANCE,
+    MAX_PAGE_SIZE, STATE,
+};
+
+const MIN_AUCTION_SECS: u64 = 60 * 60;
+const MAX_AUCTION_SECS: u64 = 7 * 24 * 60 * 60;
+// A new high bid must beat the previous one by this much, in basis points
+const MIN_BID_INCREMENT_BPS: u64 = 500;
+// A bid this close to the end pushes the end back to this far from the bid
+const ANTI_SNIPING_SECS: u64 = 5 * 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum AuctionKind {
+    // Ascending bids; the highest bid at the end wins if it meets the reserve
+    English,
+    // The price falls linearly from `start_price` to the reserve over the
+    // auction, and the first bid at the current price wins outright
+    Dutch { start_price: u64 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum AuctionStatus {
+    Open,
+    Sold,
+    Unsold,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Bid {
+    pub bidder: Principal,
+    pub amount: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Auction {
+    pub id: u64,
+    pub vibe_id: String,
+    pub seller: Principal,
+    pub kind: AuctionKind,
+    pub reserve_price: u64,
+    pub started_at: u64,
+    pub ends_at: u64,
+    pub status: AuctionStatus,
+    // Every accepted bid, oldest first. Only the last one is held in escrow
+    pub bids: Vec<Bid>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct AuctionPage {
+    pub auctions: Vec<Auction>,
+    pub next_cursor: Option<u64>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct AuctionState {
+    next_id: u64,
+    auctions: BTreeMap<u64, Auction>,
+    // Vibe ID to its open auction
+    open: BTreeMap<String, u64>,
+}
+
+impl AuctionState {
+    pub(crate) fn is_active(&self, vibe_id: &str) -> bool {
+        self.open.contains_key(vibe_id)
+    }
+
+    // Tokens held for the leading bid of every open auction
+    pub(crate) fn escrowed(&self) -> u64 {
+        self.open
+            .values()
+            .filter_map(|id| self.auctions.get(id))
+            .filter_map(|auction| auction.highest_bid())
+            .map(|bid| bid.amount)
+            .sum()
+    }
+}
+
+impl Auction {
+    fn highest_bid(&self) -> Option<&Bid> {
+        self.bids.last()
+    }
+
+    // What a bid has to be at `now` to be accepted
+    fn min_bid(&self, now: u64) -> u64 {
+        match self.kind {
+            AuctionKind::English => match self.highest_bid() {
+                Some(bid) => bid.amount + (bid.amount * MIN_BID_INCREMENT_BPS / 10_000).max(1),
+                None => 1,
+            },
+            AuctionKind::Dutch { start_price } => {
+                let duration = self.ends_at - self.started_at;
+                let elapsed = now.saturating_sub(self.started_at).min(duration);
+                let drop = (start_price - self.reserve_price) as u128 * elapsed as u128 / duration as u128;
+                start_price - drop as u64
+            }
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn schedule_settlement(auction_id: u64, delay_secs: u64) {
+    ic_cdk_timers::set_timer(std::time::Duration::from_secs(delay_secs), move || {
+        STATE.with(|state| {
+            settle_if_due(&mut state.borrow_mut(), auction_id);
+        });
+    });
+}
+
+// Tests drive settlement by advancing MOCK_TIME and calling `settle_auction`
+#[cfg(test)]
+fn schedule_settlement(_auction_id: u64, _delay_secs: u64) {}
+
+fn refund(state: &mut State, bid: &Bid) {
+    *state.token_balances.entry(bid.bidder).or_insert(INITIAL_BALANCE) += bid.amount;
+}
+
+// Cancels any open auction for a vibe that is going away, refunding the
+// escrowed bid
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    let Some(auction_id) = state.auctions.open.remove(vibe_id) else {
+        return;
+    };
+    let Some(auction) = state.auctions.auctions.get_mut(&auction_id) else {
+        return;
+    };
+    auction.status = AuctionStatus::Cancelled;
+    if let Some(bid) = auction.highest_bid().cloned() {
+        refund(state, &bid);
+    }
+}
+
+// Closes the auction if its end has passed. Anti-sniping may have moved the
+// end since the timer was set, in which case the timer is set again
+fn settle_if_due(state: &mut State, auction_id: u64) -> Option<AuctionStatus> {
+    let now = get_timestamp();
+    let auction = state.auctions.auctions.get(&auction_id)?.clone();
+    if auction.status != AuctionStatus::Open {
+        return Some(auction.status);
+    }
+    if now < auction.ends_at {
+        schedule_settlement(auction_id, auction.ends_at - now);
+        return None;
+    }
+
+    let winning = auction
+        .highest_bid()
+        .filter(|bid| bid.amount >= auction.reserve_price && is_vibe_visible(state, &auction.vibe_id))
+        .cloned();
+    let status = match winning {
+        Some(bid) => {
+            close(state, &auction, bid.bidder, bid.amount);
+            AuctionStatus::Sold
+        }
+        None => {
+            if let Some(bid) = auction.highest_bid() {
+                refund(state, bid);
+            }
+            AuctionStatus::Unsold
+        }
+    };
+
+    state.auctions.open.remove(&auction.vibe_id);
+    if let Some(auction) = state.auctions.auctions.get_mut(&auction_id) {
+        auction.status = status;
+    }
+    Some(status)
+}
+
+fn close(state: &mut State, auction: &Auction, buyer: Principal, price: u64) -> Sale {
+    let sale = marketplace::settle_sale(state, &auction.vibe_id, auction.seller, buyer, price);
+    rebuild_leaderboard(state);
+    sale
+}
+
+#[update]
+pub(crate) fn create_auction(
+    vibe_id: String,
+    kind: AuctionKind,
+    reserve_price: u64,
+    duration_secs: u64,
+) -> Result<u64, VibeError> {
+    let user = current_caller();
+    let now = get_timestamp();
+
+    let valid_kind = match kind {
+        AuctionKind::English => true,
+        AuctionKind::Dutch { start_price } => start_price > reserve_price && reserve_price > 0,
+    };
+    if !valid_kind || !(MIN_AUCTION_SECS..=MAX_AUCTION_SECS).contains(&duration_secs) {
+        return Err(VibeError::InvalidAuction);
+    }
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+        if find_vibe(&state, &vibe_id).map(|v| v.owner) != Some(user) {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if marketplace::is_escrowed(&state, &vibe_id) {
+            return Err(VibeError::AlreadyListed);
+        }
+
+        let id = state.auctions.next_id;
+        state.auctions.next_id += 1;
+        state.auctions.open.insert(vibe_id.clone(), id);
+        state.auctions.auctions.insert(id, Auction {
+            id,
+            vibe_id,
+            seller: user,
+            kind,
+            reserve_price,
+            started_at: now,
+            ends_at: now + duration_secs,
+            status: AuctionStatus::Open,
+            bids: Vec::new(),
+        });
+        schedule_settlement(id, duration_secs);
+
+        Ok(id)
+    })
+}
+
+// The bid amount is taken from the caller's balance straight away and held
+// until they are outbid or the auction closes. A Dutch bid at or above the
+// current price buys the vibe at that price
+#[update]
+pub(crate) fn place_bid(auction_id: u64, amount: u64) -> Result<AuctionStatus, VibeError> {
+    let user = current_caller();
+    let now = get_timestamp();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let auction = state.auctions.auctions.get(&auction_id).cloned().ok_or(VibeError::AuctionNotFound)?;
+        if auction.status != AuctionStatus::Open || now >= auction.ends_at {
+            return Err(VibeError::AuctionClosed);
+        }
+        if !is_vibe_visible(&state, &auction.vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+        if auction.seller == user {
+            return Err(VibeError::CannotBuyOwnVibe);
+        }
+        let min_bid = auction.min_bid(now);
+        if amount < min_bid {
+            return Err(VibeError::BidTooLow { min_bid });
+        }
+
+        // Dutch auctions charge the current price, not the amount offered
+        let charge = match auction.kind {
+            AuctionKind::English => amount,
+            AuctionKind::Dutch { .. } => min_bid,
+        };
+        // The leading bidder raising their own bid only needs to cover the
+        // difference; their held bid goes towards the new one
+        let held = auction.highest_bid()
+            .filter(|bid| bid.bidder == user)
+            .map(|bid| bid.amount)
+            .unwrap_or(0);
+        let balance = *state.token_balances.get(&user).unwrap_or(&INITIAL_BALANCE);
+        if balance + held < charge {
+            return Err(VibeError::InsufficientBalance);
+        }
+        state.token_balances.insert(user, balance + held - charge);
+
+        if let AuctionKind::Dutch { .. } = auction.kind {
+            close(&mut state, &auction, user, charge);
+            state.auctions.open.remove(&auction.vibe_id);
+            if let Some(auction) = state.auctions.auctions.get_mut(&auction_id) {
+                auction.bids.push(Bid { bidder: user, amount: charge, timestamp: now });
+                auction.status = AuctionStatus::Sold;
+            }
+            return Ok(AuctionStatus::Sold);
+        }
+
+        if let Some(previous) = auction.highest_bid().filter(|bid| bid.bidder != user) {
+            refund(&mut state, previous);
+            let kind = NotificationKind::Outbid { auction_id };
+            notifications::notify(&mut state, previous.bidder, user, kind);
+        }
+        if let Some(auction) = state.auctions.auctions.get_mut(&auction_id) {
+            auction.bids.push(Bid { bidder: user, amount, timestamp: now });
+            if auction.ends_at - now < ANTI_SNIPING_SECS {
+                auction.ends_at = now + ANTI_SNIPING_SECS;
+            }
+        }
+
+        Ok(AuctionStatus::Open)
+    })
+}
+
+// Only an auction nobody has bid on can be called off
+#[update]
+pub(crate) fn cancel_auction(auction_id: u64) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let auction = state.auctions.auctions
+            .get_mut(&auction_id)
+            .filter(|a| a.status == AuctionStatus::Open)
+            .ok_or(VibeError::AuctionNotFound)?;
+        if auction.seller != user {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if !auction.bids.is_empty() {
+            return Err(VibeError::AuctionHasBids);
+        }
+
+        auction.status = AuctionStatus::Cancelled;
+        let vibe_id = auction.vibe_id.clone();
+        state.auctions.open.remove(&vibe_id);
+        Ok(())
+    })
+}
+
+// Settlement normally runs from a timer at the end time; anyone may trigger
+// it once the auction has ended in case the timer was lost to an upgrade
+#[update]
+pub(crate) fn settle_auction(auction_id: u64) -> Result<AuctionStatus, VibeError> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        settle_if_due(&mut state, auction_id).ok_or_else(|| {
+            if state.auctions.auctions.contains_key(&auction_id) {
+                VibeError::AuctionNotEnded
+            } else {
+                VibeError::AuctionNotFound
+            }
+        })
+    })
+}
+
+#[query]
+pub(crate) fn get_auction(auction_id: u64) -> Option<Auction> {
+    STATE.with(|state| state.borrow().auctions.auctions.get(&auction_id).cloned())
+}
+
+// Current price of a Dutch auction, or the lowest acceptable next bid of an
+// English one
+#[query]
+pub(crate) fn get_auction_min_bid(auction_id: u64) -> Result<u64, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        let auction = state.auctions.auctions.get(&auction_id).ok_or(VibeError::AuctionNotFound)?;
+        Ok(auction.min_bid(get_timestamp()))
+    })
+}
+
+// Open auctions, newest first
+#[query]
+pub(crate) fn get_open_auctions(cursor: Option<u64>, limit: u32) -> AuctionPage {
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+    let upper = cursor.map_or(Bound::Unbounded, Bound::Excluded);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut open = state.auctions.auctions
+            .range((Bound::Unbounded, upper))
+            .rev()
+            .map(|(_, a)| a)
+            .filter(|a| a.status == AuctionStatus::Open && is_vibe_visible(&state, &a.vibe_id));
+
+        let auctions: Vec<Auction> = open.by_ref().take(limit).cloned().collect();
+        let next_cursor = if open.next().is_some() {
+            auctions.last().map(|a| a.id)
+        } else {
+            None
+        };
+
+        AuctionPage { auctions, next_cursor }
+    })
+}
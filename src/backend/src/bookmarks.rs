@@ -0,0 +1,279 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::Bound;
+
+use crate::error::VibeError;
+use crate::{current_caller, find_vibe, get_timestamp, is_vibe_visible, State, Vibe, MAX_PAGE_SIZE, STATE};
+
+const MAX_FOLDERS: usize = 50;
+const MAX_FOLDER_NAME_LENGTH: usize = 40;
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct SavedVibe {
+    pub bookmark_id: u64,
+    pub vibe: Vibe,
+    pub folder: Option<String>,
+    pub saved_at: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct BookmarkPage {
+    pub bookmarks: Vec<SavedVibe>,
+    pub next_cursor: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct BookmarkFolder {
+    pub name: String,
+    pub count: u64,
+}
+
+#[derive(Clone, Debug)]
+struct Bookmark {
+    vibe_id: String,
+    folder: Option<String>,
+    saved_at: u64,
+}
+
+// Bookmarks and folders are private to their owner; the only thing anyone
+// else sees is how many people saved a vibe, and that earns no tokens
+#[derive(Default, Clone)]
+pub(crate) struct BookmarkState {
+    next_id: u64,
+    // Bookmark ID order is save order, so a user's map reads oldest first
+    saved: HashMap<Principal, BTreeMap<u64, Bookmark>>,
+    by_vibe: HashMap<(Principal, String), u64>,
+    folders: HashMap<Principal, BTreeSet<String>>,
+    counts: HashMap<String, u64>,
+}
+
+impl BookmarkState {
+    pub(crate) fn count(&self, vibe_id: &str) -> u64 {
+        self.counts.get(vibe_id).copied().unwrap_or(0)
+    }
+}
+
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    state.bookmarks.counts.remove(vibe_id);
+    let keys: Vec<(Principal, String)> = state.bookmarks.by_vibe
+        .keys()
+        .filter(|(_, id)| id == vibe_id)
+        .cloned()
+        .collect();
+    for key in keys {
+        if let Some(id) = state.bookmarks.by_vibe.remove(&key) {
+            if let Some(saved) = state.bookmarks.saved.get_mut(&key.0) {
+                saved.remove(&id);
+            }
+        }
+    }
+}
+
+fn normalize_folder(name: &str) -> Result<String, VibeError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_FOLDER_NAME_LENGTH {
+        return Err(VibeError::InvalidFolderName);
+    }
+    Ok(name.to_string())
+}
+
+fn require_folder(state: &State, user: Principal, folder: Option<String>) -> Result<Option<String>, VibeError> {
+    let Some(folder) = folder else {
+        return Ok(None);
+    };
+    let folder = normalize_folder(&folder)?;
+    let exists = state.bookmarks.folders.get(&user).is_some_and(|f| f.contains(&folder));
+    if !exists {
+        return Err(VibeError::FolderNotFound);
+    }
+    Ok(Some(folder))
+}
+
+// Returns the number of users who have saved the vibe
+#[update]
+pub(crate) fn bookmark_vibe(vibe_id: String, folder: Option<String>) -> Result<u64, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+        let folder = require_folder(&state, user, folder)?;
+        let key = (user, vibe_id.clone());
+        if state.bookmarks.by_vibe.contains_key(&key) {
+            return Err(VibeError::AlreadyBookmarked);
+        }
+
+        let id = state.bookmarks.next_id;
+        state.bookmarks.next_id += 1;
+        state.bookmarks.by_vibe.insert(key, id);
+        state.bookmarks.saved.entry(user).or_default().insert(id, Bookmark {
+            vibe_id: vibe_id.clone(),
+            folder,
+            saved_at: get_timestamp(),
+        });
+
+        let count = state.bookmarks.counts.entry(vibe_id).or_insert(0);
+        *count += 1;
+        Ok(*count)
+    })
+}
+
+#[update]
+pub(crate) fn unbookmark_vibe(vibe_id: String) -> Result<u64, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let id = state.bookmarks.by_vibe
+            .remove(&(user, vibe_id.clone()))
+            .ok_or(VibeError::BookmarkNotFound)?;
+        if let Some(saved) = state.bookmarks.saved.get_mut(&user) {
+            saved.remove(&id);
+        }
+
+        let count = state.bookmarks.counts.entry(vibe_id.clone()).or_insert(0);
+        *count = count.saturating_sub(1);
+        let count = *count;
+        if count == 0 {
+            state.bookmarks.counts.remove(&vibe_id);
+        }
+        Ok(count)
+    })
+}
+
+// Moves a bookmark into a folder, or out of any folder with `None`
+#[update]
+pub(crate) fn move_bookmark(vibe_id: String, folder: Option<String>) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let folder = require_folder(&state, user, folder)?;
+        let id = *state.bookmarks.by_vibe
+            .get(&(user, vibe_id))
+            .ok_or(VibeError::BookmarkNotFound)?;
+        if let Some(bookmark) = state.bookmarks.saved.get_mut(&user).and_then(|s| s.get_mut(&id)) {
+            bookmark.folder = folder;
+        }
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn create_bookmark_folder(name: String) -> Result<(), VibeError> {
+    let user = current_caller();
+    let name = normalize_folder(&name)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let folders = state.bookmarks.folders.entry(user).or_default();
+        if folders.contains(&name) {
+            return Err(VibeError::FolderExists);
+        }
+        if folders.len() >= MAX_FOLDERS {
+            return Err(VibeError::FolderLimitReached);
+        }
+        folders.insert(name);
+        Ok(())
+    })
+}
+
+// Deleting a folder keeps its bookmarks, they just become unfiled
+#[update]
+pub(crate) fn delete_bookmark_folder(name: String) -> Result<(), VibeError> {
+    let user = current_caller();
+    let name = normalize_folder(&name)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let removed = state.bookmarks.folders
+            .get_mut(&user)
+            .map(|f| f.remove(&name))
+            .unwrap_or(false);
+        if !removed {
+            return Err(VibeError::FolderNotFound);
+        }
+        for bookmark in state.bookmarks.saved.get_mut(&user).into_iter().flat_map(|s| s.values_mut()) {
+            if bookmark.folder.as_deref() == Some(name.as_str()) {
+                bookmark.folder = None;
+            }
+        }
+        Ok(())
+    })
+}
+
+#[query]
+pub(crate) fn get_my_bookmark_folders() -> Vec<BookmarkFolder> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        for bookmark in state.bookmarks.saved.get(&user).into_iter().flat_map(|s| s.values()) {
+            if let Some(folder) = &bookmark.folder {
+                *counts.entry(folder.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        state.bookmarks.folders
+            .get(&user)
+            .into_iter()
+            .flatten()
+            .map(|name| BookmarkFolder {
+                name: name.clone(),
+                count: counts.get(name.as_str()).copied().unwrap_or(0),
+            })
+            .collect()
+    })
+}
+
+// Most recently saved first. With a folder only that folder's bookmarks are
+// returned; vibes hidden since they were saved are left out
+#[query]
+pub(crate) fn get_my_bookmarks(folder: Option<String>, cursor: Option<u64>, limit: u32) -> BookmarkPage {
+    let user = current_caller();
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+    let folder = folder.map(|f| f.trim().to_string());
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let Some(saved) = state.bookmarks.saved.get(&user) else {
+            return BookmarkPage { bookmarks: Vec::new(), next_cursor: None };
+        };
+        let upper = cursor.map_or(Bound::Unbounded, Bound::Excluded);
+
+        let mut matching = saved
+            .range((Bound::Unbounded, upper))
+            .rev()
+            .filter(|(_, b)| folder.is_none() || b.folder == folder)
+            .filter_map(|(id, b)| {
+                find_vibe(&state, &b.vibe_id)
+                    .filter(|_| is_vibe_visible(&state, &b.vibe_id))
+                    .map(|vibe| SavedVibe {
+                        bookmark_id: *id,
+                        vibe: vibe.clone(),
+                        folder: b.folder.clone(),
+                        saved_at: b.saved_at,
+                    })
+            });
+
+        let bookmarks: Vec<SavedVibe> = matching.by_ref().take(limit).collect();
+        let next_cursor = if matching.next().is_some() {
+            bookmarks.last().map(|b| b.bookmark_id)
+        } else {
+            None
+        };
+
+        BookmarkPage { bookmarks, next_cursor }
+    })
+}
@@ -0,0 +1,135 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::HashMap;
+
+use crate::error::VibeError;
+use crate::{
+    auctions, bookmarks, collections, comments, current_caller, editions, find_vibe, get_timestamp, has_engagement,
+    marketplace, moderation, pricing, reactions, rebuild_leaderboard, revisions, search, tags, tips, treasury, State,
+    Vibe, STATE,
+};
+
+// Refund policy: a vibe burned within this window of minting, before anyone
+// has liked, shared, commented on, reacted to or tipped it, gets its mint
+// price back in full.
+// Every other burn is final with no refund
+const BURN_REFUND_WINDOW_SECS: u64 = 60 * 60;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Tombstone {
+    pub vibe_id: String,
+    pub creator: Principal,
+    pub minted_at: u64,
+    pub burned_at: u64,
+    pub refund: u64,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct BurnState {
+    tombstones: HashMap<String, Tombstone>,
+}
+
+impl BurnState {
+    pub(crate) fn is_burned(&self, vibe_id: &str) -> bool {
+        self.tombstones.contains_key(vibe_id)
+    }
+}
+
+// Removes a vibe and everything that refers to it: interaction counters,
+// like/share sets, reactions, bookmarks, listings, auctions, tips, comments,
+// reports, revisions and the tag and search indexes. The caller is responsible for
+// rebuilding the leaderboard
+fn purge_vibe(state: &mut State, vibe_id: &str) -> Option<Vibe> {
+    let creator = find_vibe(state, vibe_id)?.creator;
+    let vibes = state.user_vibes.get_mut(&creator)?;
+    let position = vibes.iter().position(|v| v.id == vibe_id)?;
+    let vibe = vibes.remove(position);
+    if vibes.is_empty() {
+        state.user_vibes.remove(&creator);
+    }
+
+    state.vibe_interactions.remove(vibe_id);
+    for liked in state.user_likes.values_mut() {
+        liked.remove(vibe_id);
+    }
+    for shared in state.user_shares.values_mut() {
+        shared.remove(vibe_id);
+    }
+
+    tags::unindex_vibe(state, vibe_id, &vibe.tags);
+    search::unindex_vibe(state, vibe_id);
+    comments::remove_vibe_comments(state, vibe_id);
+    moderation::forget_vibe(state, vibe_id);
+    revisions::forget_vibe(state, vibe_id);
+    reactions::forget_vibe(state, vibe_id);
+    bookmarks::forget_vibe(state, vibe_id);
+    collections::forget_vibe(state, vibe_id);
+    marketplace::forget_vibe(state, vibe_id);
+    auctions::forget_vibe(state, vibe_id);
+    tips::forget_vibe(state, vibe_id);
+    editions::forget_vibe(state, vibe_id);
+    pricing::forget_vibe(state, vibe_id);
+
+    Some(vibe)
+}
+
+fn refund_for(state: &State, vibe: &Vibe, now: u64) -> u64 {
+    let recent = now.saturating_sub(vibe.timestamp) <= BURN_REFUND_WINDOW_SECS;
+    if recent && !has_engagement(state, &vibe.id) {
+        state.pricing.paid_for(&vibe.id)
+    } else {
+        0
+    }
+}
+
+// Purges the vibe and leaves a tombstone behind so its ID is never reused
+pub(crate) fn burn(state: &mut State, vibe_id: &str, now: u64, refund: u64) -> Option<Tombstone> {
+    let vibe = purge_vibe(state, vibe_id)?;
+    let tombstone = Tombstone {
+        vibe_id: vibe.id,
+        creator: vibe.creator,
+        minted_at: vibe.timestamp,
+        burned_at: now,
+        refund,
+    };
+    state.burns.tombstones.insert(vibe_id.to_string(), tombstone.clone());
+    Some(tombstone)
+}
+
+#[update]
+pub(crate) fn burn_vibe(vibe_id: String) -> Result<u64, VibeError> {
+    let user = current_caller();
+    let now = get_timestamp();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let vibe = find_vibe(&state, &vibe_id).ok_or(VibeError::VibeNotFound)?;
+        if vibe.owner != user {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if marketplace::is_escrowed(&state, &vibe_id) {
+            return Err(VibeError::VibeListed);
+        }
+        // Collectors paid for copies of this vibe, so it has to stay
+        if state.editions.has_collected_copies(&vibe_id) {
+            return Err(VibeError::VibeHasEditions);
+        }
+        // Only the original minter can get the mint cost back
+        let refund = if vibe.creator == user { refund_for(&state, vibe, now) } else { 0 };
+
+        let refund = treasury::pay(&mut state, user, refund);
+        burn(&mut state, &vibe_id, now, refund).ok_or(VibeError::VibeNotFound)?;
+        rebuild_leaderboard(&mut state);
+
+        Ok(refund)
+    })
+}
+
+#[query]
+pub(crate) fn get_tombstone(vibe_id: String) -> Option<Tombstone> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        state.burns.tombstones.get(&vibe_id).cloned()
+    })
+}
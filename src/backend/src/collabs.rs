@@ -0,0 +1,247 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::error::VibeError;
+use crate::notifications::{self, NotificationKind};
+use crate::rate_limit::RateLimitedAction;
+use crate::{
+    current_caller, find_vibe, find_vibe_mut, get_timestamp, next_vibe_id, publish_vibe, remixes, royalties, validation,
+    pricing, treasury, MintOptions, State, INITIAL_BALANCE, STATE,
+};
+
+const MAX_COLLABORATORS: usize = 10;
+const TOTAL_SHARE_BPS: u32 = 10_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub struct CreatorShare {
+    pub creator: Principal,
+    // Basis points; the shares of a vibe add up to 10_000
+    pub share_bps: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum CollabStatus {
+    Pending,
+    Published,
+    Declined,
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct CollabProposal {
+    pub id: u64,
+    pub proposer: Principal,
+    pub content: String,
+    pub shares: Vec<CreatorShare>,
+    pub accepted: Vec<Principal>,
+    pub status: CollabStatus,
+    pub created_at: u64,
+    // Quoted mint price, held from the proposer until the vibe is published
+    pub mint_cost: u64,
+    // Set once everyone has accepted
+    pub vibe_id: Option<String>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct CollabState {
+    next_id: u64,
+    proposals: BTreeMap<u64, (CollabProposal, MintOptions)>,
+}
+
+// Credits `amount` earned by a vibe to its creators by their agreed shares and
+// returns what each received. Rounding leftovers go to the vibe's lead
+// creator, as does everything for a solo vibe
+pub(crate) fn credit_creators(state: &mut State, vibe_id: &str, amount: u64) -> Vec<(Principal, u64)> {
+    let Some((lead, shares)) = find_vibe(state, vibe_id).map(|v| (v.creator, v.collaborators.clone())) else {
+        return Vec::new();
+    };
+
+    let mut credited = Vec::new();
+    let mut remaining = amount;
+    for share in shares.iter().filter(|s| s.creator != lead) {
+        let part = amount * share.share_bps as u64 / TOTAL_SHARE_BPS as u64;
+        credited.push((share.creator, part));
+        remaining -= part;
+    }
+    credited.insert(0, (lead, remaining));
+
+    for (creator, part) in &credited {
+        *state.token_balances.entry(*creator).or_insert(INITIAL_BALANCE) += part;
+    }
+    credited
+}
+
+fn check_shares(proposer: Principal, shares: &[CreatorShare]) -> Result<(), VibeError> {
+    let creators: BTreeSet<Principal> = shares.iter().map(|s| s.creator).collect();
+    let total: u32 = shares.iter().map(|s| s.share_bps as u32).sum();
+
+    let valid = (2..=MAX_COLLABORATORS).contains(&shares.len())
+        && creators.len() == shares.len()
+        && creators.contains(&proposer)
+        && !creators.contains(&Principal::anonymous())
+        && shares.iter().all(|s| s.share_bps > 0)
+        && total == TOTAL_SHARE_BPS;
+    if !valid {
+        return Err(VibeError::InvalidCollabShares);
+    }
+    Ok(())
+}
+
+// Publishes the vibe once every co-creator has accepted. The proposer is the
+// lead creator and first owner
+fn publish_if_ready(state: &mut State, proposal_id: u64) {
+    let Some((proposal, options)) = state.collabs.proposals.get(&proposal_id).cloned() else {
+        return;
+    };
+    let everyone = proposal.shares.iter().all(|s| proposal.accepted.contains(&s.creator));
+    if proposal.status != CollabStatus::Pending || !everyone {
+        return;
+    }
+
+    let now = get_timestamp();
+    let id = next_vibe_id(state, proposal.proposer, now);
+    publish_vibe(state, id.clone(), proposal.proposer, proposal.content.clone(), options, now);
+    pricing::record_paid(state, &id, proposal.mint_cost);
+    if let Some(vibe) = find_vibe_mut(state, &id) {
+        vibe.collaborators = proposal.shares.clone();
+    }
+
+    if let Some((proposal, _)) = state.collabs.proposals.get_mut(&proposal_id) {
+        proposal.status = CollabStatus::Published;
+        proposal.vibe_id = Some(id);
+    }
+}
+
+// The quoted mint price is taken from the proposer up front and refunded if
+// anyone declines
+#[update]
+pub(crate) fn propose_collab_vibe(
+    content: String,
+    shares: Vec<CreatorShare>,
+    options: Option<MintOptions>,
+) -> Result<u64, VibeError> {
+    let user = current_caller();
+    let now = get_timestamp();
+    let options = options.unwrap_or_default();
+    check_shares(user, &shares)?;
+    royalties::validate_royalty(options.royalty_bps.unwrap_or(0))?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let content = validation::validate_content(&state.content_rules, &content)?;
+        if let Some(parent_id) = &options.remix_of {
+            remixes::check_parent(&state, parent_id)?;
+        }
+        let price = pricing::checked_quote(&state, user, now, options.max_cost)?;
+        let balance = *state.token_balances.get(&user).unwrap_or(&INITIAL_BALANCE);
+        if balance < price {
+            return Err(VibeError::InsufficientBalance);
+        }
+
+        state.rate_limits.consume(user, RateLimitedAction::Mint, now)?;
+        state.token_balances.insert(user, balance - price);
+        treasury::deposit(&mut state, price);
+        pricing::record_mint(&mut state, user, now);
+
+        let id = state.collabs.next_id;
+        state.collabs.next_id += 1;
+        let proposal = CollabProposal {
+            id,
+            proposer: user,
+            content,
+            shares: shares.clone(),
+            accepted: vec![user],
+            status: CollabStatus::Pending,
+            created_at: now,
+            mint_cost: price,
+            vibe_id: None,
+        };
+        state.collabs.proposals.insert(id, (proposal, options));
+
+        for share in shares.iter().filter(|s| s.creator != user) {
+            let kind = NotificationKind::CollabInvite { proposal_id: id };
+            notifications::notify(&mut state, share.creator, user, kind);
+        }
+
+        Ok(id)
+    })
+}
+
+#[update]
+pub(crate) fn accept_collab(proposal_id: u64) -> Result<CollabStatus, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let (proposal, _) = state.collabs.proposals
+            .get_mut(&proposal_id)
+            .filter(|(p, _)| p.status == CollabStatus::Pending)
+            .ok_or(VibeError::CollabNotFound)?;
+        if !proposal.shares.iter().any(|s| s.creator == user) {
+            return Err(VibeError::NotCollaborator);
+        }
+        if !proposal.accepted.contains(&user) {
+            proposal.accepted.push(user);
+        }
+
+        publish_if_ready(&mut state, proposal_id);
+        Ok(state.collabs.proposals[&proposal_id].0.status)
+    })
+}
+
+// Any co-creator, the proposer included, can call the vibe off before it is
+// published
+#[update]
+pub(crate) fn decline_collab(proposal_id: u64) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let (proposal, _) = state.collabs.proposals
+            .get_mut(&proposal_id)
+            .filter(|(p, _)| p.status == CollabStatus::Pending)
+            .ok_or(VibeError::CollabNotFound)?;
+        if !proposal.shares.iter().any(|s| s.creator == user) {
+            return Err(VibeError::NotCollaborator);
+        }
+        proposal.status = CollabStatus::Declined;
+        let (proposer, mint_cost) = (proposal.proposer, proposal.mint_cost);
+
+        treasury::pay(&mut state, proposer, mint_cost);
+        Ok(())
+    })
+}
+
+#[query]
+pub(crate) fn get_collab_proposal(proposal_id: u64) -> Result<CollabProposal, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        state.collabs.proposals
+            .get(&proposal_id)
+            .map(|(p, _)| p)
+            .filter(|p| p.shares.iter().any(|s| s.creator == user))
+            .cloned()
+            .ok_or(VibeError::CollabNotFound)
+    })
+}
+
+// Pending proposals the caller is part of, oldest first
+#[query]
+pub(crate) fn get_my_collab_proposals() -> Vec<CollabProposal> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        state.collabs.proposals
+            .values()
+            .map(|(p, _)| p)
+            .filter(|p| p.status == CollabStatus::Pending && p.shares.iter().any(|s| s.creator == user))
+            .cloned()
+            .collect()
+    })
+}
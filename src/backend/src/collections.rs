@@ -0,0 +1,307 @@
+use candid::{CandidType, Deserialize, Nat, Principal};
+use ic_cdk::{query, update};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::error::VibeError;
+use crate::validation::{self, ContentRules};
+use crate::{current_caller, find_vibe, get_timestamp, is_vibe_visible, State, Vibe, STATE};
+
+const MAX_TITLE_LENGTH: u32 = 80;
+const MAX_DESCRIPTION_LENGTH: u32 = 1_000;
+const MAX_COLLECTIONS_PER_CREATOR: usize = 100;
+const MAX_VIBES_PER_COLLECTION: usize = 200;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Collection {
+    pub id: u64,
+    pub creator: Principal,
+    pub title: String,
+    pub description: String,
+    pub cover_vibe_id: Option<String>,
+    // In the creator's chosen order
+    pub vibe_ids: Vec<String>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize)]
+pub struct CollectionStats {
+    pub vibes: u64,
+    pub likes: u64,
+    pub shares: u64,
+}
+
+// The subset of the ICRC-7 metadata `Value` type that we emit, encoded the
+// same way so standard clients can decode it
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub enum MetadataValue {
+    Text(String),
+    Nat(Nat),
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct CollectionView {
+    pub collection: Collection,
+    // Visible vibes only, in collection order
+    pub vibes: Vec<Vibe>,
+    pub stats: CollectionStats,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct CollectionState {
+    next_id: u64,
+    collections: BTreeMap<u64, Collection>,
+}
+
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    for collection in state.collections.collections.values_mut() {
+        collection.vibe_ids.retain(|id| id != vibe_id);
+        if collection.cover_vibe_id.as_deref() == Some(vibe_id) {
+            collection.cover_vibe_id = None;
+        }
+    }
+}
+
+// Titles and descriptions go through the admin's content rules, with their
+// own length caps. An empty description is allowed
+fn validate_text(rules: &ContentRules, text: &str, max_length: u32, required: bool) -> Result<String, VibeError> {
+    if !required && text.trim().is_empty() {
+        return Ok(String::new());
+    }
+    let rules = ContentRules {
+        min_length: 1,
+        max_length: rules.max_length.min(max_length),
+        ..rules.clone()
+    };
+    validation::validate_content(&rules, text)
+}
+
+fn stats_for(state: &State, collection: &Collection) -> CollectionStats {
+    let mut stats = CollectionStats::default();
+    for id in collection.vibe_ids.iter().filter(|id| is_vibe_visible(state, id)) {
+        let interactions = state.vibe_interactions.get(id).cloned().unwrap_or_default();
+        stats.vibes += 1;
+        stats.likes += interactions.likes;
+        stats.shares += interactions.shares;
+    }
+    stats
+}
+
+fn owned_collection(state: &mut State, user: Principal, collection_id: u64) -> Result<&mut Collection, VibeError> {
+    let collection = state.collections.collections
+        .get_mut(&collection_id)
+        .ok_or(VibeError::CollectionNotFound)?;
+    if collection.creator != user {
+        return Err(VibeError::NotCollectionOwner);
+    }
+    Ok(collection)
+}
+
+// Collections may only hold vibes the caller created
+fn require_own_vibe(state: &State, user: Principal, vibe_id: &str) -> Result<(), VibeError> {
+    match find_vibe(state, vibe_id) {
+        Some(vibe) if vibe.creator == user => Ok(()),
+        Some(_) => Err(VibeError::NotVibeOwner),
+        None => Err(VibeError::VibeNotFound),
+    }
+}
+
+#[update]
+pub(crate) fn create_collection(title: String, description: String) -> Result<u64, VibeError> {
+    let user = current_caller();
+    let now = get_timestamp();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let title = validate_text(&state.content_rules, &title, MAX_TITLE_LENGTH, true)?;
+        let description = validate_text(&state.content_rules, &description, MAX_DESCRIPTION_LENGTH, false)?;
+        let owned = state.collections.collections.values().filter(|c| c.creator == user).count();
+        if owned >= MAX_COLLECTIONS_PER_CREATOR {
+            return Err(VibeError::CollectionLimitReached);
+        }
+
+        let id = state.collections.next_id;
+        state.collections.next_id += 1;
+        state.collections.collections.insert(id, Collection {
+            id,
+            creator: user,
+            title,
+            description,
+            cover_vibe_id: None,
+            vibe_ids: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        });
+        Ok(id)
+    })
+}
+
+#[update]
+pub(crate) fn update_collection(
+    collection_id: u64,
+    title: String,
+    description: String,
+    cover_vibe_id: Option<String>,
+) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let title = validate_text(&state.content_rules, &title, MAX_TITLE_LENGTH, true)?;
+        let description = validate_text(&state.content_rules, &description, MAX_DESCRIPTION_LENGTH, false)?;
+        let collection = owned_collection(&mut state, user, collection_id)?;
+        if cover_vibe_id.as_ref().is_some_and(|id| !collection.vibe_ids.contains(id)) {
+            return Err(VibeError::NotInCollection);
+        }
+
+        collection.title = title;
+        collection.description = description;
+        collection.cover_vibe_id = cover_vibe_id;
+        collection.updated_at = get_timestamp();
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn delete_collection(collection_id: u64) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        owned_collection(&mut state, user, collection_id)?;
+        state.collections.collections.remove(&collection_id);
+        Ok(())
+    })
+}
+
+// Inserts the vibe at `position`, or appends it when no position is given
+#[update]
+pub(crate) fn add_to_collection(collection_id: u64, vibe_id: String, position: Option<u32>) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        require_own_vibe(&state, user, &vibe_id)?;
+        let collection = owned_collection(&mut state, user, collection_id)?;
+        if collection.vibe_ids.contains(&vibe_id) {
+            return Err(VibeError::AlreadyInCollection);
+        }
+        if collection.vibe_ids.len() >= MAX_VIBES_PER_COLLECTION {
+            return Err(VibeError::CollectionLimitReached);
+        }
+
+        let position = position
+            .map(|p| (p as usize).min(collection.vibe_ids.len()))
+            .unwrap_or(collection.vibe_ids.len());
+        collection.vibe_ids.insert(position, vibe_id);
+        collection.updated_at = get_timestamp();
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn remove_from_collection(collection_id: u64, vibe_id: String) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let collection = owned_collection(&mut state, user, collection_id)?;
+        let position = collection.vibe_ids
+            .iter()
+            .position(|id| *id == vibe_id)
+            .ok_or(VibeError::NotInCollection)?;
+        collection.vibe_ids.remove(position);
+        if collection.cover_vibe_id.as_ref() == Some(&vibe_id) {
+            collection.cover_vibe_id = None;
+        }
+        collection.updated_at = get_timestamp();
+        Ok(())
+    })
+}
+
+// `vibe_ids` must list exactly the vibes already in the collection
+#[update]
+pub(crate) fn reorder_collection(collection_id: u64, vibe_ids: Vec<String>) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let collection = owned_collection(&mut state, user, collection_id)?;
+        let current: HashSet<&String> = collection.vibe_ids.iter().collect();
+        let proposed: HashSet<&String> = vibe_ids.iter().collect();
+        if vibe_ids.len() != collection.vibe_ids.len() || current != proposed {
+            return Err(VibeError::NotInCollection);
+        }
+
+        collection.vibe_ids = vibe_ids;
+        collection.updated_at = get_timestamp();
+        Ok(())
+    })
+}
+
+#[query]
+pub(crate) fn get_collection(collection_id: u64) -> Result<CollectionView, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let collection = state.collections.collections
+            .get(&collection_id)
+            .cloned()
+            .ok_or(VibeError::CollectionNotFound)?;
+        let vibes = collection.vibe_ids
+            .iter()
+            .filter(|id| is_vibe_visible(&state, id))
+            .filter_map(|id| find_vibe(&state, id).cloned())
+            .collect();
+        let stats = stats_for(&state, &collection);
+
+        Ok(CollectionView { collection, vibes, stats })
+    })
+}
+
+// A creator's collections with their stats, most recently updated first
+#[query]
+pub(crate) fn get_creator_collections(creator: Principal) -> Vec<(Collection, CollectionStats)> {
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut collections: Vec<(Collection, CollectionStats)> = state.collections.collections
+            .values()
+            .filter(|c| c.creator == creator)
+            .map(|c| (c.clone(), stats_for(&state, c)))
+            .collect();
+        collections.sort_by(|(a, _), (b, _)| b.updated_at.cmp(&a.updated_at).then(a.id.cmp(&b.id)));
+        collections
+    })
+}
+
+// ICRC-7 collection metadata, with platform-specific entries under `vibe:`
+#[query]
+pub(crate) fn get_collection_metadata(collection_id: u64) -> Result<Vec<(String, MetadataValue)>, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let collection = state.collections.collections
+            .get(&collection_id)
+            .ok_or(VibeError::CollectionNotFound)?;
+        let stats = stats_for(&state, collection);
+
+        let mut metadata = vec![
+            ("icrc7:name".to_string(), MetadataValue::Text(collection.title.clone())),
+            ("icrc7:description".to_string(), MetadataValue::Text(collection.description.clone())),
+            ("icrc7:total_supply".to_string(), MetadataValue::Nat(stats.vibes.into())),
+            ("vibe:creator".to_string(), MetadataValue::Text(collection.creator.to_text())),
+            ("vibe:likes".to_string(), MetadataValue::Nat(stats.likes.into())),
+            ("vibe:shares".to_string(), MetadataValue::Nat(stats.shares.into())),
+        ];
+        if let Some(cover) = &collection.cover_vibe_id {
+            metadata.push(("vibe:cover".to_string(), MetadataValue::Text(cover.clone())));
+        }
+        Ok(metadata)
+    })
+}
@@ -0,0 +1,210 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::access;
+use crate::error::VibeError;
+use crate::{get_timestamp, State, STATE};
+
+// Likes plus shares each side of a pair must give the other before the pair
+// is treated as a reciprocal ring
+const MUTUAL_INTERACTION_THRESHOLD: u64 = 3;
+// Multiplier applied to rewards and reputation exchanged inside a ring
+const COLLUSION_REWARD_FACTOR: f32 = 0.25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum FlagStatus {
+    PendingReview,
+    Confirmed,
+    Cleared,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct FlaggedAccount {
+    pub principal: Principal,
+    pub partners: Vec<Principal>,
+    pub flagged_at: u64,
+    pub status: FlagStatus,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct CollusionCluster {
+    pub members: Vec<Principal>,
+    // Sum of interactions exchanged along the cluster's reciprocal edges
+    pub mutual_interactions: u64,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct CollusionState {
+    flagged: HashMap<Principal, FlaggedAccount>,
+}
+
+// Likes and shares `from` has given to vibes created by `to`
+fn interactions_between(state: &State, from: &Principal, to: &Principal) -> u64 {
+    let Some(vibes) = state.user_vibes.get(to) else {
+        return 0;
+    };
+
+    let likes = state.user_likes.get(from);
+    let shares = state.user_shares.get(from);
+    vibes.iter()
+        .map(|v| {
+            likes.map(|l| l.contains(&v.id) as u64).unwrap_or(0)
+                + shares.map(|s| s.contains(&v.id) as u64).unwrap_or(0)
+        })
+        .sum()
+}
+
+fn is_reciprocal_pair(state: &State, a: &Principal, b: &Principal) -> bool {
+    a != b
+        && interactions_between(state, a, b) >= MUTUAL_INTERACTION_THRESHOLD
+        && interactions_between(state, b, a) >= MUTUAL_INTERACTION_THRESHOLD
+}
+
+fn flag(state: &mut State, principal: Principal, partner: Principal, now: u64) {
+    let entry = state.collusion.flagged
+        .entry(principal)
+        .or_insert_with(|| FlaggedAccount {
+            principal,
+            partners: Vec::new(),
+            flagged_at: now,
+            status: FlagStatus::PendingReview,
+        });
+    if !entry.partners.contains(&partner) {
+        entry.partners.push(partner);
+        // A new partner puts a previously cleared account back up for review
+        if entry.status == FlagStatus::Cleared {
+            entry.status = FlagStatus::PendingReview;
+            entry.flagged_at = now;
+        }
+    }
+}
+
+// Flagged and not cleared, whether still awaiting review or confirmed
+fn is_suspect(state: &State, principal: &Principal) -> bool {
+    state.collusion.flagged
+        .get(principal)
+        .is_some_and(|f| f.status != FlagStatus::Cleared)
+}
+
+// Called after `user` has engaged with a vibe owned by `owner`. Flags the pair
+// if the engagement closed a reciprocal ring and returns the factor to scale
+// the resulting rewards by, which is reduced whenever either side is suspect
+pub(crate) fn check_engagement(state: &mut State, user: Principal, owner: Principal) -> f32 {
+    if is_reciprocal_pair(state, &user, &owner) {
+        let now = get_timestamp();
+        flag(state, user, owner, now);
+        flag(state, owner, user, now);
+    }
+
+    if is_suspect(state, &user) || is_suspect(state, &owner) {
+        COLLUSION_REWARD_FACTOR
+    } else {
+        1.0
+    }
+}
+
+// Groups principals connected by reciprocal edges of the like/share graph
+fn detect_clusters(state: &State) -> Vec<CollusionCluster> {
+    let creators: HashMap<&str, Principal> = state.user_vibes
+        .iter()
+        .flat_map(|(creator, vibes)| vibes.iter().map(move |v| (v.id.as_str(), *creator)))
+        .collect();
+
+    let mut edges: HashMap<(Principal, Principal), u64> = HashMap::new();
+    for interactions in [&state.user_likes, &state.user_shares] {
+        for (from, vibe_ids) in interactions {
+            for id in vibe_ids {
+                if let Some(to) = creators.get(id.as_str()) {
+                    if to != from {
+                        *edges.entry((*from, *to)).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    // Union-find over principals joined by a reciprocal edge
+    let mut parent: BTreeMap<Principal, Principal> = BTreeMap::new();
+    fn root(parent: &mut BTreeMap<Principal, Principal>, p: Principal) -> Principal {
+        let next = *parent.entry(p).or_insert(p);
+        if next == p {
+            return p;
+        }
+        let r = root(parent, next);
+        parent.insert(p, r);
+        r
+    }
+
+    let mut weights: Vec<(Principal, Principal, u64)> = Vec::new();
+    for ((from, to), count) in &edges {
+        if from >= to || *count < MUTUAL_INTERACTION_THRESHOLD {
+            continue;
+        }
+        let back = edges.get(&(*to, *from)).copied().unwrap_or(0);
+        if back >= MUTUAL_INTERACTION_THRESHOLD {
+            let (a, b) = (root(&mut parent, *from), root(&mut parent, *to));
+            if a != b {
+                parent.insert(a, b);
+            }
+            weights.push((*from, *to, count + back));
+        }
+    }
+
+    let mut clusters: BTreeMap<Principal, CollusionCluster> = BTreeMap::new();
+    let members: Vec<Principal> = parent.keys().copied().collect();
+    for member in members {
+        let r = root(&mut parent, member);
+        clusters
+            .entry(r)
+            .or_insert_with(|| CollusionCluster { members: Vec::new(), mutual_interactions: 0 })
+            .members
+            .push(member);
+    }
+    for (from, _, weight) in weights {
+        let r = root(&mut parent, from);
+        if let Some(cluster) = clusters.get_mut(&r) {
+            cluster.mutual_interactions += weight;
+        }
+    }
+
+    let mut clusters: Vec<CollusionCluster> = clusters.into_values().collect();
+    clusters.sort_by_key(|c| Reverse(c.mutual_interactions));
+    clusters
+}
+
+#[query]
+pub(crate) fn get_collusion_clusters() -> Result<Vec<CollusionCluster>, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        access::require_admin(&state)?;
+        Ok(detect_clusters(&state))
+    })
+}
+
+#[query]
+pub(crate) fn get_flagged_accounts() -> Result<Vec<FlaggedAccount>, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        access::require_admin(&state)?;
+
+        let mut flagged: Vec<FlaggedAccount> = state.collusion.flagged.values().cloned().collect();
+        flagged.sort_by_key(|f| f.flagged_at);
+        Ok(flagged)
+    })
+}
+
+#[update]
+pub(crate) fn review_flagged_account(principal: Principal, cleared: bool) -> Result<(), VibeError> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        access::require_admin(&state)?;
+
+        let account = state.collusion.flagged
+            .get_mut(&principal)
+            .ok_or(VibeError::AccountNotFlagged)?;
+        account.status = if cleared { FlagStatus::Cleared } else { FlagStatus::Confirmed };
+        Ok(())
+    })
+}
@@ -0,0 +1,278 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{HashMap, HashSet};
+
+use crate::error::VibeError;
+use crate::moderation::{self, ModerationAction, Visibility};
+use crate::notifications::{self, NotificationKind};
+use crate::rate_limit::RateLimitedAction;
+use crate::{
+    collusion, current_caller, find_vibe, find_vibe_mut, get_timestamp, is_vibe_visible,
+    treasury, validation, State, MAX_PAGE_SIZE, STATE,
+};
+
+const COMMENT_REWARD_USER: u64 = 1;
+const COMMENT_REWARD_CREATOR: u64 = 1;
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Comment {
+    pub id: u64,
+    pub vibe_id: String,
+    pub parent_id: Option<u64>,
+    pub author: Principal,
+    pub text: String,
+    pub created_at: u64,
+    pub edited_at: Option<u64>,
+    pub reply_count: u64,
+    pub deleted: bool,
+    pub visibility: Visibility,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct CommentPage {
+    pub comments: Vec<Comment>,
+    // Pass back as `cursor` to fetch the next page
+    pub next_cursor: Option<u64>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct CommentState {
+    next_id: u64,
+    comments: HashMap<u64, Comment>,
+    // Comment IDs per vibe in creation order
+    by_vibe: HashMap<String, Vec<u64>>,
+    // Vibes each principal has already been rewarded for commenting on
+    rewarded: HashMap<Principal, HashSet<String>>,
+}
+
+fn is_listed(comment: &Comment) -> bool {
+    !comment.deleted && comment.visibility == Visibility::Visible
+}
+
+// Keeps `Vibe.comments` in line with the comments that are actually shown
+fn refresh_comment_count(state: &mut State, vibe_id: &str) {
+    let count = state.comments.by_vibe
+        .get(vibe_id)
+        .map(|ids| {
+            ids.iter()
+                .filter_map(|id| state.comments.comments.get(id))
+                .filter(|c| is_listed(c))
+                .count() as u64
+        })
+        .unwrap_or(0);
+
+    if let Some(vibe) = find_vibe_mut(state, vibe_id) {
+        vibe.comments = count;
+    }
+}
+
+// Drops every comment on a vibe that is going away
+pub(crate) fn remove_vibe_comments(state: &mut State, vibe_id: &str) {
+    for id in state.comments.by_vibe.remove(vibe_id).unwrap_or_default() {
+        state.comments.comments.remove(&id);
+    }
+    for rewarded in state.comments.rewarded.values_mut() {
+        rewarded.remove(vibe_id);
+    }
+}
+
+fn adjust_reply_count(state: &mut State, parent_id: Option<u64>, listed: bool) {
+    if let Some(parent) = parent_id.and_then(|id| state.comments.comments.get_mut(&id)) {
+        if listed {
+            parent.reply_count += 1;
+        } else {
+            parent.reply_count = parent.reply_count.saturating_sub(1);
+        }
+    }
+}
+
+// Pays the vibe creator the first time each principal comments on a vibe,
+// mirroring the like rewards
+fn reward_comment(state: &mut State, user: Principal, owner: Principal, vibe_id: &str) {
+    if user == owner {
+        return;
+    }
+    if !state.comments.rewarded.entry(user).or_default().insert(vibe_id.to_string()) {
+        return;
+    }
+
+    let factor = collusion::check_engagement(state, user, owner);
+    let reputation = *state.reputation.get(&owner).unwrap_or(&1.0);
+    let creator_reward = (COMMENT_REWARD_CREATOR as f32 * reputation * factor) as u64;
+    let user_reward = (COMMENT_REWARD_USER as f32 * factor) as u64;
+
+    treasury::pay(state, owner, creator_reward);
+    treasury::pay(state, user, user_reward);
+    *state.reputation.entry(user).or_insert(1.0) += 0.01 * factor;
+    *state.reputation.entry(owner).or_insert(1.0) += 0.03 * factor;
+}
+
+#[update]
+pub(crate) fn add_comment(vibe_id: String, text: String, parent_id: Option<u64>) -> Result<u64, VibeError> {
+    let user = current_caller();
+    let now = get_timestamp();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let owner = find_vibe(&state, &vibe_id)
+            .map(|v| v.creator)
+            .ok_or(VibeError::VibeNotFound)?;
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+
+        let text = validation::validate_content(&state.content_rules, &text)?;
+
+        if let Some(parent_id) = parent_id {
+            let parent = state.comments.comments
+                .get(&parent_id)
+                .ok_or(VibeError::CommentNotFound)?;
+            if parent.vibe_id != vibe_id || !is_listed(parent) {
+                return Err(VibeError::InvalidParentComment);
+            }
+        }
+
+        state.rate_limits.consume(user, RateLimitedAction::Comment, now)?;
+
+        let id = state.comments.next_id;
+        state.comments.next_id += 1;
+        state.comments.comments.insert(id, Comment {
+            id,
+            vibe_id: vibe_id.clone(),
+            parent_id,
+            author: user,
+            text: text.clone(),
+            created_at: now,
+            edited_at: None,
+            reply_count: 0,
+            deleted: false,
+            visibility: Visibility::Visible,
+        });
+        state.comments.by_vibe.entry(vibe_id.clone()).or_default().push(id);
+        adjust_reply_count(&mut state, parent_id, true);
+
+        reward_comment(&mut state, user, owner, &vibe_id);
+        refresh_comment_count(&mut state, &vibe_id);
+
+        let kind = NotificationKind::Comment { vibe_id: vibe_id.clone(), comment_id: id };
+        notifications::notify(&mut state, owner, user, kind);
+        notifications::notify_mentions(&mut state, user, &text, &vibe_id, Some(id));
+
+        Ok(id)
+    })
+}
+
+// Lists one level of a thread: top-level comments when `parent_id` is None,
+// otherwise the direct replies to that comment
+#[query]
+pub(crate) fn get_comments(
+    vibe_id: String,
+    parent_id: Option<u64>,
+    cursor: Option<u64>,
+    limit: u32,
+) -> CommentPage {
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut matching = state.comments.by_vibe
+            .get(&vibe_id)
+            .map(|ids| ids.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter(|id| cursor.is_none_or(|c| **id > c))
+            .filter_map(|id| state.comments.comments.get(id))
+            .filter(|c| c.parent_id == parent_id && c.visibility == Visibility::Visible)
+            // Deleted comments that still have replies stay as placeholders
+            .filter(|c| !c.deleted || c.reply_count > 0);
+
+        let comments: Vec<Comment> = matching.by_ref().take(limit).cloned().collect();
+        let next_cursor = match matching.next() {
+            Some(_) => comments.last().map(|c| c.id),
+            None => None,
+        };
+
+        CommentPage { comments, next_cursor }
+    })
+}
+
+#[update]
+pub(crate) fn edit_comment(comment_id: u64, text: String) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let text = validation::validate_content(&state.content_rules, &text)?;
+        let comment = state.comments.comments
+            .get_mut(&comment_id)
+            .filter(|c| !c.deleted)
+            .ok_or(VibeError::CommentNotFound)?;
+        if comment.author != user {
+            return Err(VibeError::NotCommentAuthor);
+        }
+
+        comment.text = text;
+        comment.edited_at = Some(get_timestamp());
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn delete_comment(comment_id: u64) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let comment = state.comments.comments
+            .get_mut(&comment_id)
+            .filter(|c| !c.deleted)
+            .ok_or(VibeError::CommentNotFound)?;
+        if comment.author != user {
+            return Err(VibeError::NotCommentAuthor);
+        }
+
+        // Keep the record so replies still have a parent to hang off
+        let was_listed = is_listed(comment);
+        comment.deleted = true;
+        comment.text.clear();
+        let (vibe_id, parent_id) = (comment.vibe_id.clone(), comment.parent_id);
+
+        if was_listed {
+            adjust_reply_count(&mut state, parent_id, false);
+        }
+        refresh_comment_count(&mut state, &vibe_id);
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn moderate_comment(comment_id: u64, action: ModerationAction) -> Result<(), VibeError> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        moderation::require_moderator(&state)?;
+
+        let comment = state.comments.comments
+            .get_mut(&comment_id)
+            .ok_or(VibeError::CommentNotFound)?;
+
+        let was_listed = is_listed(comment);
+        match action {
+            ModerationAction::Hide => comment.visibility = Visibility::Hidden,
+            ModerationAction::Remove => comment.visibility = Visibility::Removed,
+            ModerationAction::Restore => comment.visibility = Visibility::Visible,
+            ModerationAction::Dismiss => {}
+        }
+        let now_listed = is_listed(comment);
+        let (vibe_id, parent_id) = (comment.vibe_id.clone(), comment.parent_id);
+
+        if was_listed != now_listed {
+            adjust_reply_count(&mut state, parent_id, now_listed);
+        }
+        refresh_comment_count(&mut state, &vibe_id);
+        Ok(())
+    })
+}
@@ -0,0 +1,266 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::error::VibeError;
+use crate::{
+    collabs, current_caller, find_vibe, get_timestamp, is_vibe_visible, marketplace, rebuild_leaderboard,
+    royalties, treasury, State, INITIAL_BALANCE, STATE,
+};
+
+const MAX_EDITION_SUPPLY: u32 = 1_000;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct EditionInfo {
+    pub vibe_id: String,
+    pub creator: Principal,
+    pub price: u64,
+    pub supply: u32,
+    pub minted: u32,
+    pub sold_out: bool,
+    pub opened_at: u64,
+}
+
+// One numbered copy of a vibe, owned separately from the original. Copies
+// change hands for free with `transfer_edition` or for a price with
+// `buy_edition_copy`, which pays the creator's royalty like any resale
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct EditionCopy {
+    pub token_id: u64,
+    pub vibe_id: String,
+    pub number: u32,
+    pub supply: u32,
+    pub owner: Principal,
+    pub collected_at: u64,
+    // Set while the owner offers the copy for resale
+    pub list_price: Option<u64>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct EditionState {
+    next_token_id: u64,
+    series: HashMap<String, EditionInfo>,
+    copies: BTreeMap<u64, EditionCopy>,
+}
+
+impl EditionState {
+    pub(crate) fn has_collected_copies(&self, vibe_id: &str) -> bool {
+        self.series.get(vibe_id).is_some_and(|s| s.minted > 0)
+    }
+}
+
+// Collected copies outlive the original; the series just stops selling
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    if let Some(series) = state.editions.series.get_mut(vibe_id) {
+        series.supply = series.minted;
+        series.sold_out = true;
+    }
+}
+
+#[update]
+pub(crate) fn open_edition(vibe_id: String, price: u64, supply: u32) -> Result<EditionInfo, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+        if find_vibe(&state, &vibe_id).map(|v| v.creator) != Some(user) {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if price == 0 || !(1..=MAX_EDITION_SUPPLY).contains(&supply) {
+            return Err(VibeError::InvalidEdition);
+        }
+        if state.editions.series.contains_key(&vibe_id) {
+            return Err(VibeError::EditionExists);
+        }
+
+        let info = EditionInfo {
+            vibe_id: vibe_id.clone(),
+            creator: user,
+            price,
+            supply,
+            minted: 0,
+            sold_out: false,
+            opened_at: get_timestamp(),
+        };
+        state.editions.series.insert(vibe_id, info.clone());
+        Ok(info)
+    })
+}
+
+// Pays the creator's price for the next numbered copy; the platform fee goes
+// to the treasury as with any sale
+#[update]
+pub(crate) fn collect_edition(vibe_id: String) -> Result<EditionCopy, VibeError> {
+    let user = current_caller();
+    let now = get_timestamp();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let series = state.editions.series.get(&vibe_id).cloned().ok_or(VibeError::EditionNotFound)?;
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+        if series.sold_out {
+            return Err(VibeError::EditionSoldOut);
+        }
+        if series.creator == user {
+            return Err(VibeError::CannotBuyOwnVibe);
+        }
+        let balance = *state.token_balances.get(&user).unwrap_or(&INITIAL_BALANCE);
+        if balance < series.price {
+            return Err(VibeError::InsufficientBalance);
+        }
+
+        let fee = marketplace::platform_fee(series.price);
+        state.token_balances.insert(user, balance - series.price);
+        collabs::credit_creators(&mut state, &vibe_id, series.price - fee);
+        treasury::deposit(&mut state, fee);
+
+        let token_id = state.editions.next_token_id;
+        state.editions.next_token_id += 1;
+        let number = series.minted + 1;
+        if let Some(series) = state.editions.series.get_mut(&vibe_id) {
+            series.minted = number;
+            series.sold_out = number == series.supply;
+        }
+
+        let copy = EditionCopy {
+            token_id,
+            vibe_id,
+            number,
+            supply: series.supply,
+            owner: user,
+            collected_at: now,
+            list_price: None,
+        };
+        state.editions.copies.insert(token_id, copy.clone());
+        rebuild_leaderboard(&mut state);
+
+        Ok(copy)
+    })
+}
+
+#[update]
+pub(crate) fn transfer_edition(token_id: u64, to: Principal) -> Result<(), VibeError> {
+    let user = current_caller();
+    if to == Principal::anonymous() {
+        return Err(VibeError::AnonymousCaller);
+    }
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let copy = state.editions.copies.get_mut(&token_id).ok_or(VibeError::EditionNotFound)?;
+        if copy.owner != user {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if copy.list_price.is_some() {
+            return Err(VibeError::VibeListed);
+        }
+        copy.owner = to;
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn list_edition_copy(token_id: u64, price: u64) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let copy = state.editions.copies.get_mut(&token_id).ok_or(VibeError::EditionNotFound)?;
+        if copy.owner != user {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if price == 0 {
+            return Err(VibeError::InvalidPrice);
+        }
+        if copy.list_price.is_some() {
+            return Err(VibeError::AlreadyListed);
+        }
+        copy.list_price = Some(price);
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn delist_edition_copy(token_id: u64) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let copy = state.editions.copies.get_mut(&token_id).ok_or(VibeError::EditionNotFound)?;
+        if copy.owner != user {
+            return Err(VibeError::NotVibeOwner);
+        }
+        copy.list_price.take().ok_or(VibeError::ListingNotFound)?;
+        Ok(())
+    })
+}
+
+// Buys a listed copy. The seller is paid the price less the platform fee and
+// the creator's royalty on the original vibe
+#[update]
+pub(crate) fn buy_edition_copy(token_id: u64) -> Result<EditionCopy, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let copy = state.editions.copies.get(&token_id).cloned().ok_or(VibeError::EditionNotFound)?;
+        let price = copy.list_price.ok_or(VibeError::ListingNotFound)?;
+        if copy.owner == user {
+            return Err(VibeError::CannotBuyOwnVibe);
+        }
+        let balance = *state.token_balances.get(&user).unwrap_or(&INITIAL_BALANCE);
+        if balance < price {
+            return Err(VibeError::InsufficientBalance);
+        }
+
+        state.token_balances.insert(user, balance - price);
+        let fee = marketplace::platform_fee(price);
+        let royalty = royalties::pay_royalty(&mut state, &copy.vibe_id, copy.owner, user, price);
+        *state.token_balances.entry(copy.owner).or_insert(INITIAL_BALANCE) += price - fee - royalty;
+        treasury::deposit(&mut state, fee);
+
+        let copy = state.editions.copies.get_mut(&token_id).ok_or(VibeError::EditionNotFound)?;
+        copy.owner = user;
+        copy.list_price = None;
+        let copy = copy.clone();
+        rebuild_leaderboard(&mut state);
+
+        Ok(copy)
+    })
+}
+
+#[query]
+pub(crate) fn get_edition_info(vibe_id: String) -> Option<EditionInfo> {
+    STATE.with(|state| state.borrow().editions.series.get(&vibe_id).cloned())
+}
+
+#[query]
+pub(crate) fn get_edition_copy(token_id: u64) -> Option<EditionCopy> {
+    STATE.with(|state| state.borrow().editions.copies.get(&token_id).cloned())
+}
+
+// Copies the caller owns, in the order they were collected
+#[query]
+pub(crate) fn get_my_editions() -> Vec<EditionCopy> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        state.editions.copies
+            .values()
+            .filter(|c| c.owner == user)
+            .cloned()
+            .collect()
+    })
+}
@@ -0,0 +1,81 @@
+use candid::{CandidType, Deserialize};
+
+use crate::access::Role;
+use crate::rate_limit::RateLimitedAction;
+
+// Typed rejection returned by endpoints that can fail for a reason the
+// frontend should be able to act on
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub enum VibeError {
+    Unauthorized { required: Role },
+    AnonymousCaller,
+    LastAdmin,
+    VibeNotFound,
+    NotVibeOwner,
+    InvalidReason,
+    CannotReportOwnVibe,
+    AlreadyReported,
+    NothingToAppeal,
+    AppealPending,
+    AppealNotFound,
+    InsufficientBalance,
+    ContentTooShort { min_length: u32 },
+    ContentTooLong { max_length: u32 },
+    BlockedContent,
+    InvalidContentRules,
+    RateLimited { action: RateLimitedAction, retry_after_secs: u64 },
+    InvalidRateLimit,
+    AccountNotFlagged,
+    CommentNotFound,
+    NotCommentAuthor,
+    InvalidParentComment,
+    CannotFollow,
+    AlreadyFollowing,
+    NotFollowing,
+    InvalidHandle,
+    HandleTaken,
+    HandleReserved,
+    InvalidProfileField { field: String },
+    EditUnchanged,
+    EditTooLarge,
+    EditLimitReached,
+    AlreadyReacted,
+    ReactionNotFound,
+    AlreadyBookmarked,
+    BookmarkNotFound,
+    InvalidFolderName,
+    FolderExists,
+    FolderNotFound,
+    FolderLimitReached,
+    InvalidPrice,
+    AlreadyListed,
+    ListingNotFound,
+    CannotBuyOwnVibe,
+    VibeListed,
+    InvalidAuction,
+    AuctionNotFound,
+    AuctionClosed,
+    AuctionNotEnded,
+    AuctionHasBids,
+    BidTooLow { min_bid: u64 },
+    InvalidRoyalty { max_bps: u16 },
+    CannotTipOwnVibe,
+    InvalidTipAmount,
+    InvalidEdition,
+    EditionExists,
+    EditionNotFound,
+    EditionSoldOut,
+    VibeHasEditions,
+    RemixTooDeep,
+    InvalidRemixShare { max_bps: u16 },
+    InvalidCollabShares,
+    CollabNotFound,
+    NotCollaborator,
+    CollectionNotFound,
+    NotCollectionOwner,
+    CollectionLimitReached,
+    AlreadyInCollection,
+    NotInCollection,
+    MintCostExceeded { quoted: u64 },
+    InvalidMintPricing,
+}
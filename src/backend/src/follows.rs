@@ -0,0 +1,191 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Bound;
+
+use crate::error::VibeError;
+use crate::moderation::Visibility;
+use crate::notifications::{self, NotificationKind};
+use crate::{current_caller, Vibe, MAX_PAGE_SIZE, STATE};
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct FollowCounts {
+    pub followers: u64,
+    pub following: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct PrincipalPage {
+    pub principals: Vec<Principal>,
+    pub next_cursor: Option<Principal>,
+}
+
+// Feed position of the last vibe returned; vibes are ordered newest first
+// with the ID breaking ties between vibes minted in the same second
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, CandidType, Deserialize)]
+pub struct FeedCursor {
+    pub timestamp: u64,
+    pub vibe_id: String,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct FeedPage {
+    pub vibes: Vec<Vibe>,
+    pub next_cursor: Option<FeedCursor>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct FollowState {
+    following: HashMap<Principal, BTreeSet<Principal>>,
+    followers: HashMap<Principal, BTreeSet<Principal>>,
+}
+
+impl FollowState {
+    pub(crate) fn following(&self, user: &Principal) -> impl Iterator<Item = &Principal> {
+        self.following.get(user).into_iter().flatten()
+    }
+
+    pub(crate) fn followers(&self, user: &Principal) -> impl Iterator<Item = &Principal> {
+        self.followers.get(user).into_iter().flatten()
+    }
+}
+
+pub(crate) fn is_before_cursor(vibe: &Vibe, cursor: Option<&FeedCursor>) -> bool {
+    cursor.is_none_or(|c| (vibe.timestamp, &vibe.id) < (c.timestamp, &c.vibe_id))
+}
+
+// Sorts vibes newest first and cuts one page, with a cursor when more remain
+pub(crate) fn newest_first_page(mut vibes: Vec<&Vibe>, limit: usize) -> FeedPage {
+    vibes.sort_by(|a, b| (b.timestamp, &b.id).cmp(&(a.timestamp, &a.id)));
+
+    let has_more = vibes.len() > limit;
+    let vibes: Vec<Vibe> = vibes.into_iter().take(limit).cloned().collect();
+    let next_cursor = if has_more {
+        vibes.last().map(|v| FeedCursor {
+            timestamp: v.timestamp,
+            vibe_id: v.id.clone(),
+        })
+    } else {
+        None
+    };
+
+    FeedPage { vibes, next_cursor }
+}
+
+fn page(set: Option<&BTreeSet<Principal>>, cursor: Option<Principal>, limit: u32) -> PrincipalPage {
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+    let Some(set) = set else {
+        return PrincipalPage { principals: Vec::new(), next_cursor: None };
+    };
+
+    let start = match cursor {
+        Some(c) => Bound::Excluded(c),
+        None => Bound::Unbounded,
+    };
+    let mut range = set.range((start, Bound::Unbounded));
+    let principals: Vec<Principal> = range.by_ref().take(limit).copied().collect();
+    let next_cursor = range.next().and(principals.last().copied());
+
+    PrincipalPage { principals, next_cursor }
+}
+
+#[update]
+pub(crate) fn follow(target: Principal) -> Result<(), VibeError> {
+    let user = current_caller();
+    if user == Principal::anonymous() {
+        return Err(VibeError::AnonymousCaller);
+    }
+    if user == target || target == Principal::anonymous() {
+        return Err(VibeError::CannotFollow);
+    }
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if !state.follows.following.entry(user).or_default().insert(target) {
+            return Err(VibeError::AlreadyFollowing);
+        }
+        state.follows.followers.entry(target).or_default().insert(user);
+        notifications::notify(&mut state, target, user, NotificationKind::Follow);
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn unfollow(target: Principal) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let removed = state.follows.following
+            .get_mut(&user)
+            .map(|set| set.remove(&target))
+            .unwrap_or(false);
+        if !removed {
+            return Err(VibeError::NotFollowing);
+        }
+
+        if let Some(set) = state.follows.followers.get_mut(&target) {
+            set.remove(&user);
+        }
+        Ok(())
+    })
+}
+
+#[query]
+pub(crate) fn get_follow_counts(principal: Principal) -> FollowCounts {
+    STATE.with(|state| {
+        let state = state.borrow();
+        FollowCounts {
+            followers: state.follows.followers(&principal).count() as u64,
+            following: state.follows.following(&principal).count() as u64,
+        }
+    })
+}
+
+#[query]
+pub(crate) fn get_followers(principal: Principal, cursor: Option<Principal>, limit: u32) -> PrincipalPage {
+    STATE.with(|state| {
+        let state = state.borrow();
+        page(state.follows.followers.get(&principal), cursor, limit)
+    })
+}
+
+#[query]
+pub(crate) fn get_following(principal: Principal, cursor: Option<Principal>, limit: u32) -> PrincipalPage {
+    STATE.with(|state| {
+        let state = state.borrow();
+        page(state.follows.following.get(&principal), cursor, limit)
+    })
+}
+
+#[query]
+pub(crate) fn is_following(follower: Principal, target: Principal) -> bool {
+    STATE.with(|state| {
+        let state = state.borrow();
+        let following = state.follows.following
+            .get(&follower)
+            .map(|set| set.contains(&target))
+            .unwrap_or(false);
+        following
+    })
+}
+
+// Home timeline: visible vibes from followed creators, newest first
+#[query]
+pub(crate) fn get_following_feed(cursor: Option<FeedCursor>, limit: u32) -> FeedPage {
+    let user = current_caller();
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let vibes: Vec<&Vibe> = state.follows
+            .following(&user)
+            .filter_map(|creator| state.user_vibes.get(creator))
+            .flatten()
+            .filter(|v| v.visibility == Visibility::Visible)
+            .filter(|v| is_before_cursor(v, cursor.as_ref()))
+            .collect();
+        newest_first_page(vibes, limit)
+    })
+}
@@ -3,27 +3,82 @@
 use ic_cdk::{query, update, init};
 use candid::{CandidType, Principal, Deserialize};
 use std::cell::RefCell;
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 
-// IC time API for production
-#[cfg(not(test))]
-use ic_cdk::api::time;
+mod access;
+mod auctions;
+mod bookmarks;
+mod burn;
+mod collabs;
+mod collections;
+mod collusion;
+mod comments;
+mod editions;
+mod error;
+mod follows;
+mod marketplace;
+mod moderation;
+mod notifications;
+mod pricing;
+mod profiles;
+mod rate_limit;
+mod reactions;
+mod remixes;
+mod revisions;
+mod royalties;
+mod search;
+mod tags;
+mod tips;
+mod treasury;
+mod validation;
+
+use access::{InitArgs, Role};
+use auctions::AuctionState;
+use bookmarks::BookmarkState;
+use burn::BurnState;
+use collabs::{CollabState, CreatorShare};
+use collections::CollectionState;
+use collusion::CollusionState;
+use comments::CommentState;
+use editions::EditionState;
+use follows::FollowState;
+use marketplace::MarketplaceState;
+use error::VibeError;
+use moderation::{ModerationState, Visibility};
+use notifications::{NotificationKind, NotificationState};
+use pricing::PricingState;
+use profiles::{ProfileState, ProfileSummary};
+use rate_limit::{RateLimitState, RateLimitedAction};
+use reactions::ReactionState;
+use remixes::RemixState;
+use revisions::RevisionState;
+use royalties::RoyaltyState;
+use search::SearchState;
+use tags::TagState;
+use tips::TipState;
+use treasury::TreasuryState;
+use validation::ContentRules;
 
 thread_local! {
     static STATE: RefCell<State> = RefCell::new(State::default());
 
     // Mock time storage for tests
     #[cfg(test)]
-    static MOCK_TIME: RefCell<u64> = RefCell::new(1640995200);
+    static MOCK_TIME: RefCell<u64> = const { RefCell::new(1640995200) };
 }
 
+// Flat mint price until an admin picks another pricing strategy
 const MINT_COST: u64 = 5;
 const INITIAL_BALANCE: u64 = 100;
 const LIKE_REWARD_USER: u64 = 1;
 const LIKE_REWARD_CREATOR: u64 = 2;
 const SHARE_REWARD_USER: u64 = 2;
 const SHARE_REWARD_CREATOR: u64 = 3;
-const ANONYMOUS_PRINCIPAL: &str = "2vxsx-fae";
+// Upper bound on items returned by any paginated query
+const MAX_PAGE_SIZE: usize = 50;
+// Trending vibes are drawn from those minted within this window
+const TRENDING_WINDOW_SECS: u64 = 24 * 60 * 60;
 
 #[derive(Default, Clone)]
 struct State {
@@ -34,6 +89,31 @@ struct State {
     user_shares: HashMap<Principal, HashSet<String>>,
     reputation: HashMap<Principal, f32>,
     leaderboard: Leaderboard,
+    roles: HashMap<Principal, HashSet<Role>>,
+    moderation: ModerationState,
+    content_rules: ContentRules,
+    rate_limits: RateLimitState,
+    collusion: CollusionState,
+    comments: CommentState,
+    follows: FollowState,
+    profiles: ProfileState,
+    tags: TagState,
+    notifications: NotificationState,
+    search: SearchState,
+    revisions: RevisionState,
+    burns: BurnState,
+    reactions: ReactionState,
+    bookmarks: BookmarkState,
+    marketplace: MarketplaceState,
+    treasury: TreasuryState,
+    auctions: AuctionState,
+    royalties: RoyaltyState,
+    tips: TipState,
+    editions: EditionState,
+    remixes: RemixState,
+    collabs: CollabState,
+    collections: CollectionState,
+    pricing: PricingState,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize)]
@@ -44,6 +124,27 @@ struct Vibe {
     likes: u64,
     shares: u64,
     creator: Principal,
+    // Starts as the creator and changes hands through the marketplace
+    owner: Principal,
+    visibility: Visibility,
+    comments: u64,
+    tags: Vec<String>,
+    edited_at: Option<u64>,
+    // Creator's cut of every resale, in basis points
+    royalty_bps: u16,
+    // The vibe this one remixes, if any
+    parent_id: Option<String>,
+    // Co-creators and their agreed shares; empty for a solo vibe
+    collaborators: Vec<CreatorShare>,
+}
+
+// Optional settings for a new vibe; anything left out gets the default
+#[derive(Default, Clone, Debug, CandidType, Deserialize)]
+struct MintOptions {
+    royalty_bps: Option<u16>,
+    remix_of: Option<String>,
+    // Slippage limit: the mint fails rather than charge more than this
+    max_cost: Option<u64>,
 }
 
 #[derive(Default, Clone, Debug, CandidType, Deserialize)]
@@ -57,10 +158,23 @@ struct Leaderboard {
     top_creators: Vec<(Principal, u64)>, // (creator, total tokens)
     most_liked: Vec<(String, u64)>,      // (vibe ID, like count)
     most_shared: Vec<(String, u64)>,     // (vibe ID, share count)
+    most_tipped: Vec<(String, u64)>,     // (vibe ID, tokens tipped)
+    // Profile data for every creator referenced above, filled in on read
+    profiles: Vec<ProfileSummary>,
 }
 
 #[init]
-fn init() {
+fn init(args: Option<InitArgs>) {
+    let args = args.unwrap_or_default();
+    // The installing principal is always an admin alongside any listed ones
+    let installer = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        access::bootstrap_admins(&mut state, &args.admins);
+        access::bootstrap_admins(&mut state, &[installer]);
+    });
+
     ic_cdk::println!("Vibe canister initialized!");
 }
 
@@ -87,6 +201,46 @@ fn current_caller() -> Principal {
     }
 }
 
+fn find_vibe<'a>(state: &'a State, vibe_id: &str) -> Option<&'a Vibe> {
+    state.user_vibes
+        .values()
+        .find_map(|vibes| vibes.iter().find(|v| v.id == vibe_id))
+}
+
+fn find_vibe_mut<'a>(state: &'a mut State, vibe_id: &str) -> Option<&'a mut Vibe> {
+    state.user_vibes
+        .values_mut()
+        .find_map(|vibes| vibes.iter_mut().find(|v| v.id == vibe_id))
+}
+
+// IDs are `<principal>-<seconds>`, with a counter appended when the creator
+// already has a live or burned vibe with that ID
+fn next_vibe_id(state: &State, creator: Principal, timestamp: u64) -> String {
+    let base = format!("{}-{}", creator.to_text(), timestamp);
+    let taken = |id: &str| find_vibe(state, id).is_some() || state.burns.is_burned(id);
+
+    let mut id = base.clone();
+    let mut counter = 1;
+    while taken(&id) {
+        id = format!("{}-{}", base, counter);
+        counter += 1;
+    }
+    id
+}
+
+fn is_vibe_visible(state: &State, vibe_id: &str) -> bool {
+    find_vibe(state, vibe_id)
+        .map(|v| v.visibility == Visibility::Visible)
+        .unwrap_or(false)
+}
+
+// Whether anyone has liked, shared, commented on, reacted to or tipped the vibe
+fn has_engagement(state: &State, vibe_id: &str) -> bool {
+    find_vibe(state, vibe_id).is_some_and(|v| v.likes + v.shares + v.comments > 0)
+        || state.reactions.has_reactions(vibe_id)
+        || state.tips.has_tips(vibe_id)
+}
+
 // Helper to update Vibe objects when interactions occur
 fn update_vibe_stats(vibe_id: &str, state: &mut State, likes: u64, shares: u64) {
     for (_, vibes) in state.user_vibes.iter_mut() {
@@ -110,69 +264,121 @@ fn rebuild_leaderboard(state: &mut State) {
         .map(|(p, b)| (*p, *b))
         .collect();
 
-    creators.sort_by(|a, b| b.1.cmp(&a.1));
+    creators.sort_by_key(|(_, balance)| Reverse(*balance));
     state.leaderboard.top_creators = creators.into_iter().take(10).collect();
 
-    // Rebuild most liked vibes
+    // Rebuild most liked vibes, leaving out anything hidden by moderation
     let mut most_liked: Vec<(String, u64)> = state.vibe_interactions
         .iter()
+        .filter(|(id, _)| is_vibe_visible(state, id))
         .map(|(id, stats)| (id.clone(), stats.likes))
         .collect();
-    most_liked.sort_by(|a, b| b.1.cmp(&a.1));
+    most_liked.sort_by_key(|(_, likes)| Reverse(*likes));
     state.leaderboard.most_liked = most_liked.into_iter().take(10).collect();
 
     // Rebuild most shared vibes
     let mut most_shared: Vec<(String, u64)> = state.vibe_interactions
         .iter()
+        .filter(|(id, _)| is_vibe_visible(state, id))
         .map(|(id, stats)| (id.clone(), stats.shares))
         .collect();
-    most_shared.sort_by(|a, b| b.1.cmp(&a.1));
+    most_shared.sort_by_key(|(_, shares)| Reverse(*shares));
     state.leaderboard.most_shared = most_shared.into_iter().take(10).collect();
+
+    // Rebuild most tipped vibes
+    let mut most_tipped: Vec<(String, u64)> = state.tips
+        .vibe_totals()
+        .filter(|(id, _)| is_vibe_visible(state, id))
+        .map(|(id, total)| (id.clone(), total))
+        .collect();
+    most_tipped.sort_by_key(|(_, total)| Reverse(*total));
+    state.leaderboard.most_tipped = most_tipped.into_iter().take(10).collect();
+}
+
+// Turns validated, paid-for content into a live vibe: stored, indexed,
+// linked to its parent and announced to anyone it mentions
+fn publish_vibe(
+    state: &mut State,
+    id: String,
+    creator: Principal,
+    content: String,
+    options: MintOptions,
+    timestamp: u64,
+) {
+    let tags = tags::parse_tags(&content);
+    tags::index_vibe(state, &id, timestamp, &tags);
+    search::index_vibe(state, &id, &content);
+
+    let new_vibe = Vibe {
+        id: id.clone(),
+        content,
+        timestamp,
+        likes: 0,
+        shares: 0,
+        creator,
+        owner: creator,
+        visibility: Visibility::Visible,
+        comments: 0,
+        tags,
+        edited_at: None,
+        royalty_bps: options.royalty_bps.unwrap_or(0),
+        parent_id: options.remix_of.clone(),
+        collaborators: Vec::new(),
+    };
+
+    notifications::notify_mentions(state, creator, &new_vibe.content, &id, None);
+    if let Some(parent_id) = options.remix_of {
+        remixes::record_remix(state, &parent_id, &id);
+        if let Some(parent_creator) = find_vibe(state, &parent_id).map(|v| v.creator) {
+            let kind = NotificationKind::Remix { vibe_id: parent_id, remix_id: id.clone() };
+            notifications::notify(state, parent_creator, creator, kind);
+        }
+    }
+    state.user_vibes.entry(creator).or_default().push(new_vibe);
+
+    state.vibe_interactions.insert(
+        id,
+        InteractionStats {
+            likes: 0,
+            shares: 0,
+        },
+    );
+
+    *state.reputation.entry(creator).or_insert(1.0) += 0.1;
+    rebuild_leaderboard(state);
 }
 
 #[update]
-fn mint_vibe(content: String) -> String {
+fn mint_vibe(content: String, options: Option<MintOptions>) -> Result<String, VibeError> {
     let user = current_caller();
     let timestamp = get_timestamp();
-    let id = format!("{}-{}", user.to_text(), timestamp);
+    let options = options.unwrap_or_default();
+    royalties::validate_royalty(options.royalty_bps.unwrap_or(0))?;
 
     STATE.with(|state| {
         let mut state = state.borrow_mut();
 
-        if !state.token_balances.contains_key(&user) {
-            state.token_balances.insert(user, INITIAL_BALANCE);
+        let content = validation::validate_content(&state.content_rules, &content)?;
+        if let Some(parent_id) = &options.remix_of {
+            remixes::check_parent(&state, parent_id)?;
         }
+        let id = next_vibe_id(&state, user, timestamp);
 
-        let balance = state.token_balances.get_mut(&user).unwrap();
-        if *balance < MINT_COST {
-            panic!("Insufficient balance to mint vibe");
+        let price = pricing::checked_quote(&state, user, timestamp, options.max_cost)?;
+        let balance = *state.token_balances.get(&user).unwrap_or(&INITIAL_BALANCE);
+        if balance < price {
+            return Err(VibeError::InsufficientBalance);
         }
 
-        *balance -= MINT_COST;
+        state.rate_limits.consume(user, RateLimitedAction::Mint, timestamp)?;
+        state.token_balances.insert(user, balance - price);
+        treasury::deposit(&mut state, price);
+        pricing::record_mint(&mut state, user, timestamp);
 
-        let new_vibe = Vibe {
-            id: id.clone(),
-            content: content.clone(),
-            timestamp,
-            likes: 0,
-            shares: 0,
-            creator: user,
-        };
+        publish_vibe(&mut state, id.clone(), user, content, options, timestamp);
+        pricing::record_paid(&mut state, &id, price);
 
-        state.user_vibes.entry(user).or_default().push(new_vibe.clone());
-
-        state.vibe_interactions.insert(
-            id.clone(),
-            InteractionStats {
-                likes: 0,
-                shares: 0,
-            },
-        );
-
-        *state.reputation.entry(user).or_insert(1.0) += 0.1;
-        rebuild_leaderboard(&mut state);
-
-        id
+        Ok(id)
     })
 }
 
@@ -219,45 +425,77 @@ fn reset_account() {
 
     STATE.with(|state| {
         let mut state = state.borrow_mut();
-        state.user_vibes.remove(&user);
+        // Resetting burns every vibe the user owns without a refund; vibes
+        // they minted and sold stay with their new owners, and originals with
+        // collected editions stay because collectors paid for copies of them
+        let now = get_timestamp();
+        let vibe_ids: Vec<String> = state.user_vibes
+            .values()
+            .flatten()
+            .filter(|v| v.owner == user && !state.editions.has_collected_copies(&v.id))
+            .map(|v| v.id.clone())
+            .collect();
+        for vibe_id in vibe_ids {
+            burn::burn(&mut state, &vibe_id, now, 0);
+        }
         state.user_likes.remove(&user);
         state.user_shares.remove(&user);
-        state.token_balances.insert(user, INITIAL_BALANCE);
+        // Anything above a fresh balance goes to the treasury. A balance below
+        // it is topped back up by the treasury at most once a day, so resets
+        // can't be used as a faucet
+        let balance = *state.token_balances.get(&user).unwrap_or(&INITIAL_BALANCE);
+        let balance = if balance > INITIAL_BALANCE {
+            treasury::deposit(&mut state, balance - INITIAL_BALANCE);
+            INITIAL_BALANCE
+        } else if state.rate_limits.consume(user, RateLimitedAction::Refill, now).is_ok() {
+            balance + treasury::withdraw(&mut state, INITIAL_BALANCE - balance)
+        } else {
+            balance
+        };
+        state.token_balances.insert(user, balance);
         state.reputation.insert(user, 1.0);
         rebuild_leaderboard(&mut state);
     })
 }
 
 #[update]
-fn like_vibe(vibe_id: String) -> u64 {
+fn like_vibe(vibe_id: String) -> Result<u64, VibeError> {
     let user = current_caller();
 
     STATE.with(|state| {
         let mut state = state.borrow_mut();
 
-        if !state.token_balances.contains_key(&user) {
-            state.token_balances.insert(user, INITIAL_BALANCE);
-        }
-
-        let user_likes = state.user_likes.entry(user).or_default();
-        if user_likes.contains(&vibe_id) {
-            return state.vibe_interactions
+        if state.user_likes.get(&user).is_some_and(|likes| likes.contains(&vibe_id)) {
+            return Ok(state.vibe_interactions
                 .get(&vibe_id)
                 .map(|stats| stats.likes)
-                .unwrap_or(0);
+                .unwrap_or(0));
         }
 
-        user_likes.insert(vibe_id.clone());
+        let owner = find_vibe(&state, &vibe_id)
+            .map(|v| v.creator)
+            .ok_or(VibeError::VibeNotFound)?;
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
 
-        let owner = state.user_vibes.iter()
-            .find_map(|(_, vibes)| {
-                vibes.iter().find(|v| v.id == vibe_id).map(|v| v.creator)
-            })
-            .expect("Vibe not found");
+        // Only rewarded interactions count against the caller's quota
+        state.rate_limits.consume(user, RateLimitedAction::Like, get_timestamp())?;
+
+        state.token_balances.entry(user).or_insert(INITIAL_BALANCE);
+        state.user_likes.entry(user).or_default().insert(vibe_id.clone());
+
+        // Reciprocal like rings earn a fraction of the normal rewards
+        let factor = collusion::check_engagement(&mut state, user, owner);
 
         let reputation = *state.reputation.get(&owner).unwrap_or(&1.0);
-        let creator_reward = (LIKE_REWARD_CREATOR as f32 * reputation) as u64;
-        let user_reward = LIKE_REWARD_USER;
+        let creator_reward = (LIKE_REWARD_CREATOR as f32 * reputation * factor) as u64;
+        // A fan already paid for reacting to the vibe isn't paid again for liking it
+        let user_reward = if state.reactions.has_paid(user, &vibe_id) {
+            0
+        } else {
+            (LIKE_REWARD_USER as f32 * factor) as u64
+        };
 
         // Get current stats without holding a reference
         let current_stats = state.vibe_interactions
@@ -278,49 +516,56 @@ fn like_vibe(vibe_id: String) -> u64 {
         );
 
         // Update balances and reputation
-        *state.token_balances.entry(owner).or_insert(INITIAL_BALANCE) += creator_reward;
-        *state.token_balances.entry(user).or_insert(INITIAL_BALANCE) += user_reward;
-        *state.reputation.entry(user).or_insert(1.0) += 0.01;
-        *state.reputation.entry(owner).or_insert(1.0) += 0.05;
+        let creator_reward = treasury::withdraw(&mut state, creator_reward);
+        let paid = remixes::pay_creator_reward(&mut state, &vibe_id, creator_reward);
+        treasury::deposit(&mut state, creator_reward - paid);
+        treasury::pay(&mut state, user, user_reward);
+        *state.reputation.entry(user).or_insert(1.0) += 0.01 * factor;
+        *state.reputation.entry(owner).or_insert(1.0) += 0.05 * factor;
 
         // Update Vibe object and rebuild leaderboard
         update_vibe_stats(&vibe_id, &mut state, new_likes, current_shares);
         rebuild_leaderboard(&mut state);
 
-        new_likes
+        notifications::notify(&mut state, owner, user, NotificationKind::Like { vibe_id });
+
+        Ok(new_likes)
     })
 }
 
 #[update]
-fn share_vibe(vibe_id: String) -> u64 {
+fn share_vibe(vibe_id: String) -> Result<u64, VibeError> {
     let user = current_caller();
 
     STATE.with(|state| {
         let mut state = state.borrow_mut();
 
-        if !state.token_balances.contains_key(&user) {
-            state.token_balances.insert(user, INITIAL_BALANCE);
-        }
-
-        let user_shares = state.user_shares.entry(user).or_default();
-        if user_shares.contains(&vibe_id) {
-            return state.vibe_interactions
+        if state.user_shares.get(&user).is_some_and(|shares| shares.contains(&vibe_id)) {
+            return Ok(state.vibe_interactions
                 .get(&vibe_id)
                 .map(|stats| stats.shares)
-                .unwrap_or(0);
+                .unwrap_or(0));
+        }
+
+        let owner = find_vibe(&state, &vibe_id)
+            .map(|v| v.creator)
+            .ok_or(VibeError::VibeNotFound)?;
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
         }
 
-        user_shares.insert(vibe_id.clone());
+        // Only rewarded interactions count against the caller's quota
+        state.rate_limits.consume(user, RateLimitedAction::Share, get_timestamp())?;
+
+        state.token_balances.entry(user).or_insert(INITIAL_BALANCE);
+        state.user_shares.entry(user).or_default().insert(vibe_id.clone());
 
-        let owner = state.user_vibes.iter()
-            .find_map(|(_, vibes)| {
-                vibes.iter().find(|v| v.id == vibe_id).map(|v| v.creator)
-            })
-            .expect("Vibe not found");
+        // Reciprocal like rings earn a fraction of the normal rewards
+        let factor = collusion::check_engagement(&mut state, user, owner);
 
         let reputation = *state.reputation.get(&owner).unwrap_or(&1.0);
-        let creator_reward = (SHARE_REWARD_CREATOR as f32 * reputation) as u64;
-        let user_reward = SHARE_REWARD_USER;
+        let creator_reward = (SHARE_REWARD_CREATOR as f32 * reputation * factor) as u64;
+        let user_reward = (SHARE_REWARD_USER as f32 * factor) as u64;
 
         // Get current stats without holding a reference
         let current_stats = state.vibe_interactions
@@ -341,16 +586,20 @@ fn share_vibe(vibe_id: String) -> u64 {
         );
 
         // Update balances and reputation
-        *state.token_balances.entry(owner).or_insert(INITIAL_BALANCE) += creator_reward;
-        *state.token_balances.entry(user).or_insert(INITIAL_BALANCE) += user_reward;
-        *state.reputation.entry(user).or_insert(1.0) += 0.02;
-        *state.reputation.entry(owner).or_insert(1.0) += 0.1;
+        let creator_reward = treasury::withdraw(&mut state, creator_reward);
+        let paid = remixes::pay_creator_reward(&mut state, &vibe_id, creator_reward);
+        treasury::deposit(&mut state, creator_reward - paid);
+        treasury::pay(&mut state, user, user_reward);
+        *state.reputation.entry(user).or_insert(1.0) += 0.02 * factor;
+        *state.reputation.entry(owner).or_insert(1.0) += 0.1 * factor;
 
         // Update Vibe object and rebuild leaderboard
         update_vibe_stats(&vibe_id, &mut state, current_likes, new_shares);
         rebuild_leaderboard(&mut state);
 
-        new_shares
+        notifications::notify(&mut state, owner, user, NotificationKind::Share { vibe_id });
+
+        Ok(new_shares)
     })
 }
 
@@ -369,7 +618,57 @@ fn get_vibe_stats(vibe_id: String) -> (u64, u64) {
 fn get_leaderboard() -> Leaderboard {
     STATE.with(|state| {
         let state = state.borrow();
-        state.leaderboard.clone()
+        let mut leaderboard = state.leaderboard.clone();
+
+        let vibe_creators = leaderboard.most_liked
+            .iter()
+            .chain(leaderboard.most_shared.iter())
+            .chain(leaderboard.most_tipped.iter())
+            .filter_map(|(id, _)| find_vibe(&state, id).map(|v| v.creator));
+        let mut principals: Vec<Principal> = leaderboard.top_creators
+            .iter()
+            .map(|(p, _)| *p)
+            .chain(vibe_creators)
+            .collect();
+        principals.sort();
+        principals.dedup();
+
+        leaderboard.profiles = principals
+            .into_iter()
+            .map(|p| state.profiles.summary(p))
+            .collect();
+        leaderboard
+    })
+}
+
+// Engagement score for trending: shares count double and bookmarks triple,
+// since saving a vibe says more about it than a passing like
+fn trending_score(state: &State, vibe: &Vibe) -> u64 {
+    vibe.likes + 2 * vibe.shares + 3 * state.bookmarks.count(&vibe.id)
+}
+
+#[query]
+fn get_trending_vibes(limit: u32) -> Vec<Vibe> {
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+    let since = get_timestamp().saturating_sub(TRENDING_WINDOW_SECS);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut trending: Vec<(u64, &Vibe)> = state.user_vibes
+            .values()
+            .flatten()
+            .filter(|v| v.timestamp >= since && v.visibility == Visibility::Visible)
+            .map(|v| (trending_score(&state, v), v))
+            .filter(|(score, _)| *score > 0)
+            .collect();
+        trending.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| (b.timestamp, &b.id).cmp(&(a.timestamp, &a.id)))
+        });
+
+        trending.into_iter().take(limit).map(|(_, v)| v.clone()).collect()
     })
 }
 
@@ -386,6 +685,7 @@ fn stake_tokens(amount: u64) {
         }
 
         *balance -= amount;
+        treasury::deposit(&mut state, amount);
     });
 }
 
@@ -397,20 +697,45 @@ fn claim_staking_rewards() -> u64 {
         let mut state = state.borrow_mut();
         let rewards = 5; // Placeholder
 
-        *state.token_balances.entry(user).or_insert(0) += rewards;
-        rewards
+        treasury::pay(&mut state, user, rewards)
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::access::{get_my_roles, get_roles, grant_role, revoke_role};
+    use crate::auctions::*;
+    use crate::bookmarks::*;
+    use crate::burn::*;
+    use crate::collabs::*;
+    use crate::collections::*;
+    use crate::collusion::*;
+    use crate::comments::*;
+    use crate::editions::*;
+    use crate::error::VibeError;
+    use crate::follows::*;
+    use crate::marketplace::*;
+    use crate::moderation::*;
+    use crate::notifications::*;
+    use crate::pricing::*;
+    use crate::profiles::*;
+    use crate::rate_limit::*;
+    use crate::reactions::*;
+    use crate::remixes::*;
+    use crate::revisions::*;
+    use crate::royalties::*;
+    use crate::search::*;
+    use crate::tags::*;
+    use crate::tips::*;
+    use crate::treasury::*;
+    use crate::validation::*;
     use candid::export_service;
     use candid::Principal;
     use std::cell::RefCell;
 
     thread_local! {
-        static TEST_CALLER: RefCell<Principal> = RefCell::new(Principal::anonymous());
+        static TEST_CALLER: RefCell<Principal> = const { RefCell::new(Principal::anonymous()) };
     }
 
     pub fn test_caller() -> Principal {
@@ -442,7 +767,7 @@ mod tests {
         let user2 = Principal::management_canister();
 
         set_caller(user1);
-        let vibe_id = mint_vibe("Test vibe".to_string());
+        let vibe_id = mint_vibe("Test vibe".to_string(), None).unwrap();
 
         STATE.with(|s| {
             let state = s.borrow();
@@ -455,7 +780,7 @@ mod tests {
         });
 
         set_caller(user2);
-        let likes = like_vibe(vibe_id.clone());
+        let likes = like_vibe(vibe_id.clone()).unwrap();
         assert_eq!(likes, 1);
 
         STATE.with(|s| {
@@ -478,7 +803,7 @@ mod tests {
         });
 
         set_caller(user2);
-        let shares = share_vibe(vibe_id.clone());
+        let shares = share_vibe(vibe_id.clone()).unwrap();
         assert_eq!(shares, 1);
 
         STATE.with(|s| {
@@ -512,24 +837,24 @@ mod tests {
         let user3 = Principal::from_slice(&[3; 29]);
 
         set_caller(user1);
-        let vibe_id1 = mint_vibe("First vibe".to_string());
-        let vibe_id2 = mint_vibe("Second vibe".to_string());
+        let vibe_id1 = mint_vibe("First vibe".to_string(), None).unwrap();
+        let vibe_id2 = mint_vibe("Second vibe".to_string(), None).unwrap();
 
         // First user likes vibe1
         set_caller(user2);
-        like_vibe(vibe_id1.clone());
+        like_vibe(vibe_id1.clone()).unwrap();
 
         // Second user likes vibe1
         set_caller(user3);
-        like_vibe(vibe_id1.clone());
+        like_vibe(vibe_id1.clone()).unwrap();
 
         // First user shares vibe1
         set_caller(user2);
-        share_vibe(vibe_id1.clone());
+        share_vibe(vibe_id1.clone()).unwrap();
 
         // First user likes vibe2
         set_caller(user2);
-        like_vibe(vibe_id2.clone());
+        like_vibe(vibe_id2.clone()).unwrap();
 
         STATE.with(|s| {
             let state = s.borrow();
@@ -554,4 +879,1462 @@ mod tests {
             assert_eq!(most_shared_entry.1, 1, "Expected 1 share");
         });
     }
+
+    #[test]
+    fn test_role_grants_and_guards() {
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let moderator = Principal::from_slice(&[2; 29]);
+
+        set_caller(admin);
+        init(None);
+        assert_eq!(get_my_roles(), vec![Role::Admin]);
+
+        // Non-admins cannot manage roles
+        set_caller(moderator);
+        assert_eq!(
+            grant_role(moderator, Role::Admin),
+            Err(VibeError::Unauthorized { required: Role::Admin })
+        );
+
+        set_caller(admin);
+        assert_eq!(grant_role(moderator, Role::Moderator), Ok(()));
+        STATE.with(|s| {
+            let state = s.borrow();
+            assert!(access::has_role(&state, &moderator, Role::Moderator));
+            assert!(!access::has_role(&state, &moderator, Role::Curator));
+            // Admins hold every role implicitly
+            assert!(access::has_role(&state, &admin, Role::Curator));
+        });
+
+        // The only admin cannot be demoted
+        assert_eq!(revoke_role(admin, Role::Admin), Err(VibeError::LastAdmin));
+
+        assert_eq!(revoke_role(moderator, Role::Moderator), Ok(()));
+        assert!(get_roles(moderator).is_empty());
+    }
+
+    #[test]
+    fn test_report_hide_and_appeal() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let creator = Principal::from_slice(&[2; 29]);
+        let reporter = Principal::from_slice(&[3; 29]);
+
+        set_caller(admin);
+        init(None);
+
+        set_caller(creator);
+        let vibe_id = mint_vibe("Questionable vibe".to_string(), None).unwrap();
+        assert_eq!(
+            report_vibe(vibe_id.clone(), "spam".to_string()),
+            Err(VibeError::CannotReportOwnVibe)
+        );
+
+        set_caller(reporter);
+        like_vibe(vibe_id.clone()).unwrap();
+        assert_eq!(report_vibe(vibe_id.clone(), "spam".to_string()), Ok(1));
+        assert_eq!(
+            report_vibe(vibe_id.clone(), "spam again".to_string()),
+            Err(VibeError::AlreadyReported)
+        );
+        assert!(get_moderation_queue().is_err());
+
+        set_caller(admin);
+        let queue = get_moderation_queue().unwrap();
+        assert_eq!(queue.len(), 1);
+        assert_eq!(moderate_vibe(vibe_id.clone(), ModerationAction::Hide), Ok(()));
+        assert!(get_moderation_queue().unwrap().is_empty());
+
+        // Hidden vibes drop out of the leaderboard
+        let leaderboard = get_leaderboard();
+        assert!(!leaderboard.most_liked.iter().any(|(id, _)| *id == vibe_id));
+
+        set_caller(creator);
+        assert_eq!(appeal_vibe(vibe_id.clone(), "It was satire".to_string()), Ok(()));
+        assert_eq!(
+            appeal_vibe(vibe_id.clone(), "Please".to_string()),
+            Err(VibeError::AppealPending)
+        );
+
+        set_caller(admin);
+        assert_eq!(get_pending_appeals().unwrap().len(), 1);
+        assert_eq!(resolve_appeal(vibe_id.clone(), true), Ok(()));
+
+        let leaderboard = get_leaderboard();
+        assert!(leaderboard.most_liked.iter().any(|(id, _)| *id == vibe_id));
+        assert_eq!(get_my_vibes().len(), 0);
+        set_caller(creator);
+        assert_eq!(get_my_vibes()[0].visibility, Visibility::Visible);
+    }
+
+    #[test]
+    fn test_content_validation() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let creator = Principal::from_slice(&[2; 29]);
+
+        set_caller(admin);
+        init(None);
+        assert_eq!(
+            set_content_rules(ContentRules {
+                min_length: 3,
+                max_length: 20,
+                blocked_words: vec!["scam".to_string(), "buy now".to_string()],
+                blocked_patterns: vec!["free*tokens".to_string()],
+            }),
+            Ok(())
+        );
+        let unmatchable = ContentRules { blocked_words: vec!["?!".to_string()], ..ContentRules::default() };
+        assert_eq!(set_content_rules(unmatchable), Err(VibeError::InvalidContentRules));
+
+        set_caller(creator);
+        assert_eq!(
+            set_content_rules(ContentRules::default()),
+            Err(VibeError::Unauthorized { required: Role::Admin })
+        );
+        assert_eq!(mint_vibe("  \u{0}\u{7}  ".to_string(), None), Err(VibeError::ContentTooShort { min_length: 3 }));
+        assert_eq!(mint_vibe("a".repeat(21), None), Err(VibeError::ContentTooLong { max_length: 20 }));
+        assert_eq!(mint_vibe("Total SCAM here".to_string(), None), Err(VibeError::BlockedContent));
+        assert_eq!(mint_vibe("get FREE vibe tokens".to_string(), None), Err(VibeError::BlockedContent));
+        // Phrases match across any spacing or punctuation between their words
+        assert_eq!(mint_vibe("Buy... NOW!".to_string(), None), Err(VibeError::BlockedContent));
+        // Blocked words only match whole words
+        assert!(mint_vibe("scampi night".to_string(), None).is_ok());
+
+        // Control characters are stripped and content is NFC-normalized
+        set_mock_time(1640995201);
+        mint_vibe(" cafe\u{301}\u{1b} ok ".to_string(), None).unwrap();
+        let vibes = get_my_vibes();
+        assert_eq!(vibes[1].content, "caf\u{e9} ok");
+
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 2 * MINT_COST);
+    }
+
+    #[test]
+    fn test_mint_rate_limit() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let creator = Principal::from_slice(&[2; 29]);
+
+        set_caller(admin);
+        init(None);
+        let limit = RateLimit { capacity: 2, refill_secs: 60 };
+        assert_eq!(set_rate_limit(RateLimitedAction::Mint, limit), Ok(()));
+
+        set_caller(creator);
+        mint_vibe("First".to_string(), None).unwrap();
+        set_mock_time(1640995210);
+        mint_vibe("Second".to_string(), None).unwrap();
+
+        set_mock_time(1640995220);
+        assert_eq!(
+            mint_vibe("Third".to_string(), None),
+            Err(VibeError::RateLimited { action: RateLimitedAction::Mint, retry_after_secs: 40 })
+        );
+        // A rejected mint is not charged
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 2 * MINT_COST);
+
+        let quota = get_my_quota();
+        let mint_quota = quota.iter().find(|q| q.action == RateLimitedAction::Mint).unwrap();
+        assert_eq!(mint_quota.remaining, 0);
+        assert_eq!(mint_quota.next_refill_in, 40);
+
+        set_mock_time(1640995260);
+        assert!(mint_vibe("Third".to_string(), None).is_ok());
+    }
+
+    #[test]
+    fn test_engagement_rate_limit() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let creator = Principal::from_slice(&[2; 29]);
+        let fan = Principal::from_slice(&[3; 29]);
+
+        set_caller(admin);
+        init(None);
+        let limit = RateLimit { capacity: 2, refill_secs: 10 };
+        assert_eq!(set_rate_limit(RateLimitedAction::Like, limit), Ok(()));
+        let limit = RateLimit { capacity: 1, refill_secs: 30 };
+        assert_eq!(set_rate_limit(RateLimitedAction::Share, limit), Ok(()));
+
+        // Vibe ids are per second, so space the mints out
+        set_caller(creator);
+        let first = mint_vibe("First".to_string(), None).unwrap();
+        set_mock_time(1640995201);
+        let second = mint_vibe("Second".to_string(), None).unwrap();
+        set_mock_time(1640995202);
+        let third = mint_vibe("Third".to_string(), None).unwrap();
+
+        set_caller(fan);
+        assert_eq!(like_vibe(first.clone()), Ok(1));
+        assert_eq!(like_vibe(second.clone()), Ok(1));
+        // Repeat likes pay nothing, so they don't use up the quota either
+        assert_eq!(like_vibe(first.clone()), Ok(1));
+        assert_eq!(
+            like_vibe(third.clone()),
+            Err(VibeError::RateLimited { action: RateLimitedAction::Like, retry_after_secs: 10 })
+        );
+        assert_eq!(like_vibe("missing".to_string()), Err(VibeError::VibeNotFound));
+        assert_eq!(get_vibe_stats(third.clone()), (0, 0));
+        let balance = get_my_balance();
+
+        assert_eq!(share_vibe(first.clone()), Ok(1));
+        assert_eq!(
+            share_vibe(second.clone()),
+            Err(VibeError::RateLimited { action: RateLimitedAction::Share, retry_after_secs: 30 })
+        );
+        assert_eq!(get_my_balance(), balance + SHARE_REWARD_USER);
+
+        set_mock_time(1640995212);
+        assert_eq!(like_vibe(third.clone()), Ok(1));
+        assert_eq!(
+            share_vibe(second.clone()),
+            Err(VibeError::RateLimited { action: RateLimitedAction::Share, retry_after_secs: 20 })
+        );
+        set_mock_time(1640995232);
+        assert_eq!(share_vibe(second), Ok(1));
+        assert_eq!(get_vibe_stats(third), (1, 0));
+    }
+
+    #[test]
+    fn test_reciprocal_likes_are_flagged() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let alice = Principal::from_slice(&[2; 29]);
+        let bob = Principal::from_slice(&[3; 29]);
+
+        set_caller(admin);
+        init(None);
+
+        let mut alice_vibes = Vec::new();
+        let mut bob_vibes = Vec::new();
+        for i in 0..3 {
+            set_mock_time(1640995200 + i);
+            set_caller(alice);
+            alice_vibes.push(mint_vibe(format!("Alice vibe {}", i), None).unwrap());
+            set_caller(bob);
+            bob_vibes.push(mint_vibe(format!("Bob vibe {}", i), None).unwrap());
+        }
+
+        for i in 0..2 {
+            set_caller(alice);
+            like_vibe(bob_vibes[i].clone()).unwrap();
+            set_caller(bob);
+            like_vibe(alice_vibes[i].clone()).unwrap();
+        }
+        set_caller(alice);
+        like_vibe(bob_vibes[2].clone()).unwrap();
+        assert_eq!(get_flagged_accounts().unwrap_err(), VibeError::Unauthorized { required: Role::Admin });
+
+        // Bob's third like closes the ring and pays out at the reduced rate
+        set_caller(bob);
+        let before = get_my_balance();
+        like_vibe(alice_vibes[2].clone()).unwrap();
+        assert_eq!(get_my_balance(), before);
+
+        set_caller(admin);
+        let flagged = get_flagged_accounts().unwrap();
+        assert_eq!(flagged.len(), 2);
+        assert!(flagged.iter().all(|f| f.status == FlagStatus::PendingReview));
+
+        let clusters = get_collusion_clusters().unwrap();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].members.len(), 2);
+        assert_eq!(clusters[0].mutual_interactions, 6);
+
+        assert_eq!(review_flagged_account(alice, false), Ok(()));
+        assert_eq!(review_flagged_account(admin, true), Err(VibeError::AccountNotFlagged));
+
+        // A confirmed account earns reduced rewards from everyone, not just
+        // its ring partner, and so does whoever it engages with
+        let carol = Principal::from_slice(&[4; 29]);
+        let dave = Principal::from_slice(&[5; 29]);
+        set_caller(carol);
+        let carol_vibe = mint_vibe("Carol vibe".to_string(), None).unwrap();
+        let carol_balance = get_my_balance();
+        set_caller(alice);
+        let alice_balance = get_my_balance();
+        share_vibe(carol_vibe.clone()).unwrap();
+        assert_eq!(get_my_balance(), alice_balance);
+        set_caller(carol);
+        assert_eq!(get_my_balance(), carol_balance);
+
+        set_caller(dave);
+        share_vibe(carol_vibe).unwrap();
+        assert_eq!(get_my_balance(), INITIAL_BALANCE + SHARE_REWARD_USER);
+        set_caller(carol);
+        assert_eq!(get_my_balance(), carol_balance + SHARE_REWARD_CREATOR);
+    }
+
+    #[test]
+    fn test_threaded_comments() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let fan = Principal::from_slice(&[2; 29]);
+
+        set_caller(creator);
+        let vibe_id = mint_vibe("Discuss".to_string(), None).unwrap();
+
+        set_caller(fan);
+        let first = add_comment(vibe_id.clone(), "First!".to_string(), None).unwrap();
+        let second = add_comment(vibe_id.clone(), "Second".to_string(), None).unwrap();
+        add_comment(vibe_id.clone(), "Third".to_string(), None).unwrap();
+        // Only the first comment on a vibe is rewarded
+        assert_eq!(get_my_balance(), INITIAL_BALANCE + 1);
+
+        set_caller(creator);
+        let reply = add_comment(vibe_id.clone(), "Thanks".to_string(), Some(first)).unwrap();
+        assert_eq!(
+            add_comment("missing".to_string(), "Hello".to_string(), None),
+            Err(VibeError::VibeNotFound)
+        );
+        assert_eq!(get_my_vibes()[0].comments, 4);
+
+        let page = get_comments(vibe_id.clone(), None, None, 2);
+        assert_eq!(page.comments.iter().map(|c| c.id).collect::<Vec<_>>(), vec![first, second]);
+        assert_eq!(page.comments[0].reply_count, 1);
+        let page = get_comments(vibe_id.clone(), None, page.next_cursor, 2);
+        assert_eq!(page.comments.len(), 1);
+        assert_eq!(page.next_cursor, None);
+        let replies = get_comments(vibe_id.clone(), Some(first), None, 10);
+        assert_eq!(replies.comments[0].id, reply);
+
+        assert_eq!(edit_comment(first, "Hijacked".to_string()), Err(VibeError::NotCommentAuthor));
+        set_caller(fan);
+        assert_eq!(edit_comment(first, "First, edited".to_string()), Ok(()));
+        assert_eq!(delete_comment(first), Ok(()));
+
+        // The deleted parent stays as an empty placeholder for its reply
+        let page = get_comments(vibe_id.clone(), None, None, 10);
+        assert!(page.comments[0].deleted);
+        assert!(page.comments[0].text.is_empty());
+
+        set_caller(creator);
+        assert_eq!(get_my_vibes()[0].comments, 3);
+    }
+
+    #[test]
+    fn test_following_feed() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let reader = Principal::from_slice(&[1; 29]);
+        let alice = Principal::from_slice(&[2; 29]);
+        let bob = Principal::from_slice(&[3; 29]);
+        let carol = Principal::from_slice(&[4; 29]);
+
+        for (i, creator) in [alice, bob, carol, alice].iter().enumerate() {
+            set_mock_time(1640995200 + i as u64);
+            set_caller(*creator);
+            mint_vibe(format!("Vibe {}", i), None).unwrap();
+        }
+
+        set_caller(reader);
+        assert_eq!(follow(reader), Err(VibeError::CannotFollow));
+        assert_eq!(follow(alice), Ok(()));
+        assert_eq!(follow(alice), Err(VibeError::AlreadyFollowing));
+        assert_eq!(follow(bob), Ok(()));
+        assert_eq!(get_follow_counts(reader), FollowCounts { followers: 0, following: 2 });
+        assert_eq!(get_followers(alice, None, 10).principals, vec![reader]);
+
+        // Newest first, carol is not followed
+        let page = get_following_feed(None, 2);
+        let contents: Vec<&str> = page.vibes.iter().map(|v| v.content.as_str()).collect();
+        assert_eq!(contents, vec!["Vibe 3", "Vibe 1"]);
+        let page = get_following_feed(page.next_cursor, 2);
+        assert_eq!(page.vibes.len(), 1);
+        assert_eq!(page.vibes[0].content, "Vibe 0");
+        assert!(page.next_cursor.is_none());
+
+        assert_eq!(unfollow(alice), Ok(()));
+        assert_eq!(unfollow(alice), Err(VibeError::NotFollowing));
+        assert_eq!(get_following_feed(None, 10).vibes.len(), 1);
+    }
+
+    #[test]
+    fn test_profiles_and_handles() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let alice = Principal::from_slice(&[2; 29]);
+        let bob = Principal::from_slice(&[3; 29]);
+
+        set_caller(admin);
+        init(None);
+        assert_eq!(reserve_handle("founder".to_string()), Ok(()));
+
+        set_caller(alice);
+        assert_eq!(set_handle("1alice".to_string()), Err(VibeError::InvalidHandle));
+        assert_eq!(set_handle("admin".to_string()), Err(VibeError::HandleReserved));
+        assert_eq!(set_handle("founder".to_string()), Err(VibeError::HandleReserved));
+        assert_eq!(set_handle("@Alice".to_string()), Ok("alice".to_string()));
+        let profile = update_profile(ProfileUpdate {
+            display_name: Some("  Alice \u{7}".to_string()),
+            bio: Some("Vibing".to_string()),
+            avatar: None,
+            links: vec!["https://example.com/alice".to_string()],
+        })
+        .unwrap();
+        assert_eq!(profile.display_name, Some("Alice".to_string()));
+        assert_eq!(
+            update_profile(ProfileUpdate { links: vec!["http://insecure".to_string()], ..Default::default() }),
+            Err(VibeError::InvalidProfileField { field: "links".to_string() })
+        );
+        mint_vibe("Hello".to_string(), None).unwrap();
+
+        set_caller(bob);
+        assert_eq!(set_handle("ALICE".to_string()), Err(VibeError::HandleTaken));
+        assert!(!is_handle_available("alice".to_string()));
+
+        // Renaming frees the old handle
+        set_caller(alice);
+        set_handle("alice_v2".to_string()).unwrap();
+        assert!(is_handle_available("alice".to_string()));
+        assert_eq!(get_profile_by_handle("@alice_v2".to_string()).unwrap().principal, alice);
+
+        let leaderboard = get_leaderboard();
+        let summary = leaderboard.profiles.iter().find(|p| p.principal == alice).unwrap();
+        assert_eq!(summary.handle, Some("alice_v2".to_string()));
+        assert_eq!(summary.display_name, Some("Alice".to_string()));
+    }
+
+    #[test]
+    fn test_hashtag_index() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        assert_eq!(
+            parse_tags("#Sunset vibes at the #beach, #sunset again, not a tag: a#b or #1"),
+            vec!["sunset".to_string(), "beach".to_string()]
+        );
+
+        let alice = Principal::from_slice(&[1; 29]);
+        let bob = Principal::from_slice(&[2; 29]);
+
+        set_caller(alice);
+        let old = mint_vibe("Old #summer memories".to_string(), None).unwrap();
+
+        set_mock_time(1640995200 + 2 * 24 * 60 * 60);
+        let first = mint_vibe("#summer #music".to_string(), None).unwrap();
+        set_mock_time(1640995200 + 2 * 24 * 60 * 60 + 1);
+        set_caller(bob);
+        let second = mint_vibe("More #Summer".to_string(), None).unwrap();
+
+        let page = get_vibes_by_tag("#SUMMER".to_string(), None, 2);
+        assert_eq!(page.vibes.iter().map(|v| v.id.clone()).collect::<Vec<_>>(), vec![second, first]);
+        let page = get_vibes_by_tag("summer".to_string(), page.next_cursor, 2);
+        assert_eq!(page.vibes[0].id, old);
+
+        let suggestions = autocomplete_tags("su".to_string(), 5);
+        assert_eq!(suggestions, vec![TagCount { tag: "summer".to_string(), count: 3 }]);
+
+        // The old vibe has aged out of the trending window
+        let trending = get_trending_tags(5);
+        assert_eq!(trending[0], TagCount { tag: "summer".to_string(), count: 2 });
+        assert_eq!(trending[1], TagCount { tag: "music".to_string(), count: 1 });
+
+        set_caller(alice);
+        reset_account();
+        assert!(autocomplete_tags("mu".to_string(), 5).is_empty());
+    }
+
+    #[test]
+    fn test_mentions_and_notifications() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let alice = Principal::from_slice(&[1; 29]);
+        let bob = Principal::from_slice(&[2; 29]);
+        let carol = Principal::from_slice(&[3; 29]);
+
+        set_caller(bob);
+        set_handle("bob".to_string()).unwrap();
+
+        set_caller(alice);
+        let text = format!("Hey @Bob and @{}, also @nobody", carol.to_text());
+        let vibe_id = mint_vibe(text, None).unwrap();
+
+        set_caller(bob);
+        follow(alice).unwrap();
+        like_vibe(vibe_id.clone()).unwrap();
+        share_vibe(vibe_id.clone()).unwrap();
+        add_comment(vibe_id.clone(), "Thanks @bob".to_string(), None).unwrap();
+
+        let page = get_notifications(None, 10);
+        assert_eq!(page.notifications.len(), 1);
+        assert_eq!(
+            page.notifications[0].kind,
+            NotificationKind::Mention { vibe_id: vibe_id.clone(), comment_id: None }
+        );
+        assert_eq!(page.unread, 1);
+
+        set_caller(carol);
+        assert_eq!(get_unread_count(), 1);
+
+        // Self-mentions by bob don't notify him, but alice hears about everything
+        set_caller(alice);
+        let page = get_notifications(None, 2);
+        assert_eq!(page.unread, 4);
+        assert!(matches!(page.notifications[0].kind, NotificationKind::Comment { .. }));
+        assert_eq!(page.notifications[1].kind, NotificationKind::Share { vibe_id: vibe_id.clone() });
+        let rest = get_notifications(page.next_cursor, 10);
+        assert_eq!(rest.notifications[0].kind, NotificationKind::Like { vibe_id: vibe_id.clone() });
+        assert_eq!(rest.notifications[1].kind, NotificationKind::Follow);
+        assert!(rest.next_cursor.is_none());
+
+        assert_eq!(mark_notifications_read(Some(rest.notifications[0].id)), 2);
+        assert_eq!(get_unread_count(), 2);
+        assert_eq!(mark_notifications_read(None), 2);
+        assert_eq!(get_unread_count(), 0);
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let alice = Principal::from_slice(&[1; 29]);
+        let bob = Principal::from_slice(&[2; 29]);
+
+        set_caller(alice);
+        let quiet = mint_vibe("Ocean waves at sunrise".to_string(), None).unwrap();
+        set_mock_time(1640995201);
+        let popular = mint_vibe("Sunrise over the ocean".to_string(), None).unwrap();
+        set_mock_time(1640995202);
+        let prefix_only = mint_vibe("Oceanic dreams".to_string(), None).unwrap();
+        set_mock_time(1640995203);
+        mint_vibe("Mountain air".to_string(), None).unwrap();
+
+        set_caller(bob);
+        like_vibe(popular.clone()).unwrap();
+        share_vibe(popular.clone()).unwrap();
+
+        // Engagement breaks the tie between equally good text matches
+        let page = search_vibes("ocean sunrise".to_string(), None, 10);
+        let ids: Vec<String> = page.hits.iter().map(|h| h.vibe.id.clone()).collect();
+        assert_eq!(ids, vec![popular.clone(), quiet.clone()]);
+
+        // Exact terms outrank prefix expansions
+        let page = search_vibes("Ocean".to_string(), None, 2);
+        assert_eq!(page.hits.len(), 2);
+        assert_eq!(page.hits[0].vibe.id, popular);
+        let page = search_vibes("ocean".to_string(), page.next_cursor, 2);
+        assert_eq!(page.hits.len(), 1);
+        assert_eq!(page.hits[0].vibe.id, prefix_only);
+
+        assert!(search_vibes("the".to_string(), None, 10).hits.is_empty());
+
+        set_caller(alice);
+        reset_account();
+        assert!(search_vibes("ocean".to_string(), None, 10).hits.is_empty());
+    }
+
+    #[test]
+    fn test_edit_vibe_history() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let fan = Principal::from_slice(&[2; 29]);
+
+        set_caller(creator);
+        let vibe_id = mint_vibe("Morning #coffee thoughts".to_string(), None).unwrap();
+
+        set_mock_time(1640995260);
+        assert_eq!(edit_vibe(vibe_id.clone(), "Evening #tea thoughts".to_string()), Ok(2));
+        assert!(get_vibes_by_tag("coffee".to_string(), None, 10).vibes.is_empty());
+        assert_eq!(get_vibes_by_tag("tea".to_string(), None, 10).vibes.len(), 1);
+        assert_eq!(search_vibes("evening".to_string(), None, 10).hits.len(), 1);
+
+        set_caller(fan);
+        assert_eq!(edit_vibe(vibe_id.clone(), "Hijacked".to_string()), Err(VibeError::NotVibeOwner));
+        like_vibe(vibe_id.clone()).unwrap();
+
+        // After engagement only small corrections are allowed
+        set_caller(creator);
+        assert_eq!(
+            edit_vibe(vibe_id.clone(), "Buy my totally different product now".to_string()),
+            Err(VibeError::EditTooLarge)
+        );
+        set_mock_time(1640995320);
+        assert_eq!(edit_vibe(vibe_id.clone(), "Evening #tea thoughts!".to_string()), Ok(3));
+
+        let history = get_vibe_history(vibe_id.clone()).unwrap();
+        assert_eq!(
+            history,
+            vec![
+                Revision { content: "Morning #coffee thoughts".to_string(), timestamp: 1640995200 },
+                Revision { content: "Evening #tea thoughts".to_string(), timestamp: 1640995260 },
+                Revision { content: "Evening #tea thoughts!".to_string(), timestamp: 1640995320 },
+            ]
+        );
+        let vibe = &get_my_vibes()[0];
+        assert_eq!(vibe.edited_at, Some(1640995320));
+        assert_eq!(vibe.likes, 1);
+
+        for i in 0..2 {
+            edit_vibe(vibe_id.clone(), format!("Evening #tea thoughts{}", i)).unwrap();
+        }
+        assert_eq!(
+            edit_vibe(vibe_id.clone(), "Evening #tea thoughts".to_string()),
+            Err(VibeError::EditLimitReached)
+        );
+
+        // Reactions and tips count as engagement too
+        let reacted = mint_vibe("Sunset over the bay".to_string(), None).unwrap();
+        let tipped = mint_vibe("Sunrise over the hills".to_string(), None).unwrap();
+        set_caller(fan);
+        react_vibe(reacted.clone(), Reaction::Fire).unwrap();
+        unreact_vibe(reacted.clone(), Reaction::Fire).unwrap();
+        tip_vibe(tipped.clone(), 5, None).unwrap();
+        set_caller(creator);
+        for vibe_id in [reacted, tipped] {
+            assert_eq!(
+                edit_vibe(vibe_id, "Buy my totally different product now".to_string()),
+                Err(VibeError::EditTooLarge)
+            );
+        }
+    }
+
+    #[test]
+    fn test_burn_vibe() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let fan = Principal::from_slice(&[2; 29]);
+
+        set_caller(creator);
+        let fresh = mint_vibe("Oops #typo".to_string(), None).unwrap();
+        let liked = mint_vibe("Keeper #vibes".to_string(), None).unwrap();
+        // Same-second mints get distinct IDs
+        assert_eq!(liked, format!("{}-1", fresh));
+
+        set_caller(fan);
+        like_vibe(liked.clone()).unwrap();
+        add_comment(liked.clone(), "Nice".to_string(), None).unwrap();
+        assert_eq!(burn_vibe(liked.clone()), Err(VibeError::NotVibeOwner));
+
+        // An untouched vibe burned right away is refunded
+        set_caller(creator);
+        let balance = get_my_balance();
+        assert_eq!(burn_vibe(fresh.clone()), Ok(MINT_COST));
+        assert_eq!(get_my_balance(), balance + MINT_COST);
+
+        // An engaged vibe is burned without a refund and leaves no traces
+        assert_eq!(burn_vibe(liked.clone()), Ok(0));
+        assert_eq!(burn_vibe(liked.clone()), Err(VibeError::VibeNotFound));
+        assert!(get_my_vibes().is_empty());
+        assert_eq!(get_vibe_stats(liked.clone()), (0, 0));
+        assert!(get_vibes_by_tag("vibes".to_string(), None, 10).vibes.is_empty());
+        assert!(search_vibes("keeper".to_string(), None, 10).hits.is_empty());
+        assert!(get_comments(liked.clone(), None, None, 10).comments.is_empty());
+        let leaderboard = get_leaderboard();
+        assert!(leaderboard.most_liked.is_empty());
+
+        let tombstone = get_tombstone(liked.clone()).unwrap();
+        assert_eq!(tombstone.creator, creator);
+        assert_eq!(tombstone.refund, 0);
+
+        // Burned IDs are never handed out again
+        let reminted = mint_vibe("Again".to_string(), None).unwrap();
+        assert_eq!(reminted, format!("{}-2", fresh));
+
+        // Reactions and tips also cost the refund
+        let reacted = mint_vibe("Sunset over the bay".to_string(), None).unwrap();
+        let tipped = mint_vibe("Sunrise over the hills".to_string(), None).unwrap();
+        set_caller(fan);
+        react_vibe(reacted.clone(), Reaction::Fire).unwrap();
+        tip_vibe(tipped.clone(), 5, None).unwrap();
+        set_caller(creator);
+        assert_eq!(burn_vibe(reacted), Ok(0));
+        assert_eq!(burn_vibe(tipped), Ok(0));
+
+        set_caller(fan);
+        STATE.with(|s| assert!(s.borrow().user_likes.get(&fan).unwrap().is_empty()));
+    }
+
+    #[test]
+    fn test_reactions() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let fan = Principal::from_slice(&[2; 29]);
+
+        set_caller(creator);
+        let vibe_id = mint_vibe("React to this".to_string(), None).unwrap();
+        let creator_balance = get_my_balance();
+
+        set_caller(fan);
+        let fan_balance = get_my_balance();
+        assert_eq!(like_vibe(vibe_id.clone()), Ok(1));
+        assert_eq!(react_vibe(vibe_id.clone(), Reaction::Fire), Ok(1));
+        assert_eq!(react_vibe(vibe_id.clone(), Reaction::Fire), Err(VibeError::AlreadyReacted));
+        assert_eq!(react_vibe(vibe_id.clone(), Reaction::Laugh), Ok(1));
+        assert_eq!(react_vibe("missing".to_string(), Reaction::Wow), Err(VibeError::VibeNotFound));
+
+        // The fan is paid once for the like and both reactions. The creator
+        // earns per reaction, weighted and scaled by reputation: 2 for the like
+        // (1.0), then 2 * 1.5 * 1.05 = 3 for Fire and 2 * 1.0 * 1.08 = 2 for Laugh
+        assert_eq!(get_my_balance(), fan_balance + LIKE_REWARD_USER);
+        set_caller(creator);
+        assert_eq!(get_my_balance(), creator_balance + 7);
+
+        set_caller(fan);
+        let summary = get_reactions(vibe_id.clone());
+        assert_eq!(summary.likes, 1);
+        assert_eq!(summary.reactions, vec![
+            ReactionCount { reaction: Reaction::Fire, count: 1 },
+            ReactionCount { reaction: Reaction::Laugh, count: 1 },
+        ]);
+        assert_eq!(summary.mine, vec![Reaction::Fire, Reaction::Laugh]);
+
+        // Removing and re-adding a reaction earns nothing more
+        assert_eq!(unreact_vibe(vibe_id.clone(), Reaction::Fire), Ok(0));
+        assert_eq!(unreact_vibe(vibe_id.clone(), Reaction::Fire), Err(VibeError::ReactionNotFound));
+        set_caller(creator);
+        let creator_balance = get_my_balance();
+        set_caller(fan);
+        assert_eq!(react_vibe(vibe_id.clone(), Reaction::Fire), Ok(1));
+        set_caller(creator);
+        assert_eq!(get_my_balance(), creator_balance);
+
+        // Reacting first and liking afterwards also pays the fan once
+        let late_fan = Principal::from_slice(&[3; 29]);
+        set_caller(late_fan);
+        assert_eq!(react_vibe(vibe_id.clone(), Reaction::Wow), Ok(1));
+        assert_eq!(like_vibe(vibe_id.clone()), Ok(2));
+        assert_eq!(get_my_balance(), INITIAL_BALANCE + LIKE_REWARD_USER);
+        set_caller(creator);
+
+        // Burning a vibe clears its reactions
+        assert!(burn_vibe(vibe_id.clone()).is_ok());
+        let summary = get_reactions(vibe_id);
+        assert!(summary.reactions.is_empty());
+        assert_eq!(summary.likes, 0);
+    }
+
+    #[test]
+    fn test_bookmarks() {
+        set_mock_time(1_700_000_000);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let reader = Principal::from_slice(&[2; 29]);
+
+        set_caller(creator);
+        let first = mint_vibe("First saved vibe".to_string(), None).unwrap();
+        set_mock_time(1_700_000_010);
+        let second = mint_vibe("Second saved vibe".to_string(), None).unwrap();
+        set_mock_time(1_700_000_020);
+        let unsaved = mint_vibe("Nobody saves this one".to_string(), None).unwrap();
+
+        set_caller(reader);
+        assert_eq!(like_vibe(unsaved.clone()), Ok(1));
+        let balance = get_my_balance();
+        assert_eq!(create_bookmark_folder(" Music ".to_string()), Ok(()));
+        assert_eq!(create_bookmark_folder("Music".to_string()), Err(VibeError::FolderExists));
+        assert_eq!(bookmark_vibe(first.clone(), Some("Music".to_string())), Ok(1));
+        assert_eq!(bookmark_vibe(second.clone(), None), Ok(1));
+        assert_eq!(bookmark_vibe(second.clone(), None), Err(VibeError::AlreadyBookmarked));
+        assert_eq!(bookmark_vibe(unsaved.clone(), Some("Art".to_string())), Err(VibeError::FolderNotFound));
+
+        // Bookmarks earn nothing for either side
+        assert_eq!(get_my_balance(), balance);
+
+        // Newest saved first, paginated, and filterable by folder
+        let page = get_my_bookmarks(None, None, 1);
+        assert_eq!(page.bookmarks[0].vibe.id, second);
+        let page = get_my_bookmarks(None, page.next_cursor, 1);
+        assert_eq!(page.bookmarks[0].vibe.id, first);
+        assert_eq!(page.next_cursor, None);
+        let music = get_my_bookmarks(Some("Music".to_string()), None, 10);
+        assert_eq!(music.bookmarks.len(), 1);
+        assert_eq!(get_my_bookmark_folders(), vec![BookmarkFolder { name: "Music".to_string(), count: 1 }]);
+
+        // Bookmarks are private to their owner
+        set_caller(creator);
+        assert!(get_my_bookmarks(None, None, 10).bookmarks.is_empty());
+
+        // Saved vibes rank above ones that were only liked
+        let trending = get_trending_vibes(10);
+        assert_eq!(trending.len(), 3);
+        assert!(trending.iter().position(|v| v.id == unsaved) > trending.iter().position(|v| v.id == first));
+
+        set_caller(reader);
+        assert_eq!(move_bookmark(first.clone(), None), Ok(()));
+        assert_eq!(delete_bookmark_folder("Music".to_string()), Ok(()));
+        assert!(get_my_bookmark_folders().is_empty());
+        assert_eq!(unbookmark_vibe(first.clone()), Ok(0));
+        assert_eq!(unbookmark_vibe(first), Err(VibeError::BookmarkNotFound));
+        assert_eq!(get_my_bookmarks(None, None, 10).bookmarks.len(), 1);
+    }
+
+    #[test]
+    fn test_marketplace() {
+        set_mock_time(1_710_000_000);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let seller = Principal::from_slice(&[1; 29]);
+        let buyer = Principal::from_slice(&[2; 29]);
+
+        set_caller(seller);
+        let cheap = mint_vibe("Cheap #art".to_string(), None).unwrap();
+        set_mock_time(1_710_000_010);
+        let pricey = mint_vibe("Pricey #art".to_string(), None).unwrap();
+        set_mock_time(1_710_000_020);
+        let other = mint_vibe("Not art".to_string(), None).unwrap();
+
+        assert_eq!(list_vibe(cheap.clone(), 0), Err(VibeError::InvalidPrice));
+        assert_eq!(list_vibe(cheap.clone(), 40), Ok(()));
+        assert_eq!(list_vibe(cheap.clone(), 40), Err(VibeError::AlreadyListed));
+        assert_eq!(list_vibe(pricey.clone(), 500), Ok(()));
+        assert_eq!(list_vibe(other.clone(), 10), Ok(()));
+        assert_eq!(delist_vibe(other.clone()), Ok(()));
+
+        // Listed vibes are in escrow
+        assert_eq!(burn_vibe(cheap.clone()), Err(VibeError::VibeListed));
+        assert_eq!(edit_vibe(cheap.clone(), "Cheaper #art".to_string()), Err(VibeError::VibeListed));
+
+        let filter = ListingFilter { tag: Some("#art".to_string()), ..Default::default() };
+        let page = get_listings(filter, ListingSort::PriceDescending, None, 10);
+        let ids: Vec<&str> = page.listings.iter().map(|l| l.vibe.id.as_str()).collect();
+        assert_eq!(ids, vec![pricey.as_str(), cheap.as_str()]);
+        let filter = ListingFilter { max_price: Some(100), ..Default::default() };
+        assert_eq!(get_listings(filter, ListingSort::Newest, None, 10).listings.len(), 1);
+
+        assert_eq!(buy_vibe(cheap.clone()).map(|_| ()), Err(VibeError::CannotBuyOwnVibe));
+        let seller_balance = get_my_balance();
+
+        set_caller(buyer);
+        assert_eq!(buy_vibe(pricey.clone()).map(|_| ()), Err(VibeError::InsufficientBalance));
+        let sale = buy_vibe(cheap.clone()).unwrap();
+        assert_eq!(sale.fee, 1);
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 40);
+        assert_eq!(get_my_owned_vibes().iter().map(|v| v.id.clone()).collect::<Vec<_>>(), vec![cheap.clone()]);
+        assert_eq!(buy_vibe(cheap.clone()).map(|_| ()), Err(VibeError::ListingNotFound));
+        assert_eq!(get_sale_history(cheap.clone()), vec![sale]);
+
+        // The seller is paid less the fee and can no longer touch the vibe
+        set_caller(seller);
+        assert_eq!(get_my_balance(), seller_balance + 39);
+        assert_eq!(list_vibe(cheap.clone(), 50), Err(VibeError::NotVibeOwner));
+        assert_eq!(burn_vibe(cheap.clone()), Err(VibeError::NotVibeOwner));
+        // The treasury holds its reserve, the three mint fees and the sale fee
+        assert_eq!(treasury_balance(), TREASURY_RESERVE + 3 * MINT_COST + 1);
+
+        // Resetting the seller's account leaves the sold vibe with its buyer
+        reset_account();
+        set_caller(buyer);
+        assert_eq!(get_my_owned_vibes().len(), 1);
+        assert_eq!(get_listings(ListingFilter::default(), ListingSort::Newest, None, 10).listings.len(), 0);
+    }
+
+    #[test]
+    fn test_auctions() {
+        let start = 1_720_000_000;
+        set_mock_time(start);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let seller = Principal::from_slice(&[1; 29]);
+        let alice = Principal::from_slice(&[2; 29]);
+        let bob = Principal::from_slice(&[3; 29]);
+
+        set_caller(seller);
+        let english = mint_vibe("Going once".to_string(), None).unwrap();
+        let dutch = mint_vibe("Going down".to_string(), None).unwrap();
+        let unsold = mint_vibe("Nobody wants this".to_string(), None).unwrap();
+        assert_eq!(create_auction(english.clone(), AuctionKind::English, 20, 60), Err(VibeError::InvalidAuction));
+        let english_id = create_auction(english.clone(), AuctionKind::English, 20, 3600).unwrap();
+        assert_eq!(list_vibe(english.clone(), 10), Err(VibeError::AlreadyListed));
+        assert_eq!(burn_vibe(english.clone()), Err(VibeError::VibeListed));
+        let dutch_id = create_auction(dutch.clone(), AuctionKind::Dutch { start_price: 90 }, 10, 3600).unwrap();
+        let unsold_id = create_auction(unsold.clone(), AuctionKind::English, 50, 3600).unwrap();
+        let seller_balance = get_my_balance();
+
+        // Bids are escrowed and the outbid bidder is refunded
+        set_caller(alice);
+        assert_eq!(place_bid(english_id, 10), Ok(AuctionStatus::Open));
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 10);
+        assert_eq!(place_bid(unsold_id, 30), Ok(AuctionStatus::Open));
+        set_caller(bob);
+        assert_eq!(place_bid(english_id, 10), Err(VibeError::BidTooLow { min_bid: 11 }));
+        assert_eq!(place_bid(english_id, 15), Ok(AuctionStatus::Open));
+        set_caller(alice);
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 30);
+
+        // A late bid pushes the end out
+        set_mock_time(start + 3500);
+        assert_eq!(place_bid(english_id, 25), Ok(AuctionStatus::Open));
+        assert_eq!(get_auction(english_id).unwrap().ends_at, start + 3500 + 300);
+        set_mock_time(start + 3600);
+        assert_eq!(settle_auction(english_id), Err(VibeError::AuctionNotEnded));
+        set_caller(bob);
+        assert_eq!(get_my_balance(), INITIAL_BALANCE);
+
+        // Halfway through, the Dutch price has dropped halfway to the reserve
+        set_mock_time(start + 1800);
+        assert_eq!(get_auction_min_bid(dutch_id), Ok(50));
+        assert_eq!(place_bid(dutch_id, 60), Ok(AuctionStatus::Sold));
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 50);
+        assert_eq!(place_bid(dutch_id, 60), Err(VibeError::AuctionClosed));
+
+        set_mock_time(start + 4000);
+        assert_eq!(settle_auction(english_id), Ok(AuctionStatus::Sold));
+        assert_eq!(settle_auction(unsold_id), Ok(AuctionStatus::Unsold));
+        assert!(get_open_auctions(None, 10).auctions.is_empty());
+
+        // Alice won the English auction and got her losing bid back
+        set_caller(alice);
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 25);
+        assert_eq!(get_my_owned_vibes()[0].id, english);
+        assert_eq!(get_sale_history(english).len(), 1);
+
+        // The seller keeps the unsold vibe and is paid for both sales less the
+        // 2.5% fee, which rounds down to nothing on the smaller one
+        set_caller(seller);
+        assert_eq!(get_my_balance(), seller_balance + 25 + 50 - 1);
+        assert_eq!(get_my_owned_vibes().iter().map(|v| v.id.clone()).collect::<Vec<_>>(), vec![unsold.clone()]);
+
+        // The leading bidder can raise their own bid with the held one counted
+        // towards it, and isn't told they were outbid
+        let relist_id = create_auction(unsold, AuctionKind::English, 50, 3600).unwrap();
+        set_caller(alice);
+        let notifications = get_notifications(None, 50).notifications.len();
+        assert_eq!(place_bid(relist_id, 60), Ok(AuctionStatus::Open));
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 25 - 60);
+        assert_eq!(place_bid(relist_id, 70), Ok(AuctionStatus::Open));
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 25 - 70);
+        assert_eq!(place_bid(relist_id, 100), Err(VibeError::InsufficientBalance));
+        assert_eq!(get_notifications(None, 50).notifications.len(), notifications);
+        assert_eq!(get_auction(relist_id).unwrap().bids.len(), 2);
+    }
+
+    #[test]
+    fn test_royalties() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let collector = Principal::from_slice(&[2; 29]);
+        let flipper = Principal::from_slice(&[3; 29]);
+
+        set_caller(creator);
+        let too_high = MintOptions { royalty_bps: Some(MAX_ROYALTY_BPS + 1), ..Default::default() };
+        assert_eq!(
+            mint_vibe("Greedy".to_string(), Some(too_high)),
+            Err(VibeError::InvalidRoyalty { max_bps: MAX_ROYALTY_BPS })
+        );
+        let options = MintOptions { royalty_bps: Some(1_000), ..Default::default() };
+        let vibe_id = mint_vibe("Royalty bearing".to_string(), Some(options)).unwrap();
+
+        // The primary sale pays no royalty
+        assert_eq!(list_vibe(vibe_id.clone(), 40), Ok(()));
+        set_caller(collector);
+        assert_eq!(buy_vibe(vibe_id.clone()).unwrap().royalty, 0);
+
+        // Resales pay the creator out of the seller's proceeds
+        assert_eq!(list_vibe(vibe_id.clone(), 80), Ok(()));
+        set_caller(creator);
+        let creator_balance = get_my_balance();
+        set_caller(flipper);
+        let sale = buy_vibe(vibe_id.clone()).unwrap();
+        assert_eq!((sale.fee, sale.royalty), (2, 8));
+        set_caller(collector);
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 40 + 80 - 2 - 8);
+
+        set_caller(creator);
+        assert_eq!(get_my_balance(), creator_balance + 8);
+        let report = get_my_royalty_earnings();
+        assert_eq!((report.total_earned, report.sales), (8, 1));
+        assert_eq!(report.payments[0].seller, collector);
+    }
+
+    #[test]
+    fn test_tipping() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let fan = Principal::from_slice(&[2; 29]);
+
+        set_caller(creator);
+        let vibe_id = mint_vibe("Tip jar".to_string(), None).unwrap();
+        assert_eq!(tip_vibe(vibe_id.clone(), 5, None), Err(VibeError::CannotTipOwnVibe));
+        let balance = get_my_balance();
+        let reputation = get_my_reputation();
+
+        set_caller(fan);
+        assert_eq!(tip_vibe(vibe_id.clone(), 0, None), Err(VibeError::InvalidTipAmount));
+        assert_eq!(tip_vibe(vibe_id.clone(), INITIAL_BALANCE + 1, None), Err(VibeError::InsufficientBalance));
+        assert_eq!(tip_vibe(vibe_id.clone(), 10, Some("Love it".to_string())), Ok(10));
+        assert_eq!(tip_vibe(vibe_id.clone(), 15, Some("  ".to_string())), Ok(25));
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 25);
+
+        let recent = get_recent_tips(vibe_id.clone(), 10);
+        assert_eq!(recent.len(), 2);
+        assert_eq!((recent[0].amount, recent[0].memo.clone()), (15, None));
+        assert_eq!(recent[1].memo, Some("Love it".to_string()));
+        assert_eq!(get_vibe_tip_stats(vibe_id.clone()), TipStats { total: 25, count: 2 });
+        assert_eq!(get_creator_tip_stats(creator), TipStats { total: 25, count: 2 });
+        assert_eq!(get_leaderboard().most_tipped[0], (vibe_id.clone(), 25));
+
+        set_caller(creator);
+        assert_eq!(get_my_balance(), balance + 25);
+        assert!(get_my_reputation() > reputation);
+        let page = get_notifications(None, 10);
+        assert_eq!(page.notifications[0].kind, NotificationKind::Tip { vibe_id, amount: 15 });
+    }
+
+    #[test]
+    fn test_limited_editions() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let alice = Principal::from_slice(&[2; 29]);
+        let bob = Principal::from_slice(&[3; 29]);
+
+        set_caller(creator);
+        let options = MintOptions { royalty_bps: Some(1_000), ..Default::default() };
+        let vibe_id = mint_vibe("Collect me".to_string(), Some(options)).unwrap();
+        assert_eq!(open_edition(vibe_id.clone(), 10, 0).map(|_| ()), Err(VibeError::InvalidEdition));
+        assert!(open_edition(vibe_id.clone(), 40, 2).is_ok());
+        assert_eq!(open_edition(vibe_id.clone(), 10, 5).map(|_| ()), Err(VibeError::EditionExists));
+        assert_eq!(collect_edition(vibe_id.clone()).map(|_| ()), Err(VibeError::CannotBuyOwnVibe));
+        let balance = get_my_balance();
+
+        set_caller(alice);
+        let first = collect_edition(vibe_id.clone()).unwrap();
+        assert_eq!((first.number, first.supply), (1, 2));
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 40);
+
+        set_caller(bob);
+        let second = collect_edition(vibe_id.clone()).unwrap();
+        assert_eq!(second.number, 2);
+        let info = get_edition_info(vibe_id.clone()).unwrap();
+        assert_eq!((info.minted, info.sold_out), (2, true));
+        assert_eq!(collect_edition(vibe_id.clone()).map(|_| ()), Err(VibeError::EditionSoldOut));
+
+        // Copies are owned on their own and can change hands
+        assert_eq!(transfer_edition(first.token_id, bob), Err(VibeError::NotVibeOwner));
+        assert_eq!(transfer_edition(second.token_id, alice), Ok(()));
+        assert!(get_my_editions().is_empty());
+        set_caller(alice);
+        assert_eq!(get_my_editions().len(), 2);
+        assert_eq!(get_edition_copy(second.token_id).unwrap().owner, alice);
+
+        // The creator is paid less the platform fee and can't burn or edit the original
+        set_caller(creator);
+        assert_eq!(get_my_balance(), balance + 2 * (40 - 1));
+        assert_eq!(burn_vibe(vibe_id.clone()), Err(VibeError::VibeHasEditions));
+        assert_eq!(edit_vibe(vibe_id.clone(), "Collect me!".to_string()), Err(VibeError::VibeHasEditions));
+        let creator_balance = get_my_balance();
+
+        // Reselling a copy pays the seller less the fee and the 10% royalty
+        set_caller(alice);
+        assert_eq!(list_edition_copy(second.token_id, 0), Err(VibeError::InvalidPrice));
+        assert_eq!(list_edition_copy(second.token_id, 50), Ok(()));
+        assert_eq!(transfer_edition(second.token_id, bob), Err(VibeError::VibeListed));
+        assert_eq!(buy_edition_copy(second.token_id).map(|_| ()), Err(VibeError::CannotBuyOwnVibe));
+        let alice_balance = get_my_balance();
+        set_caller(bob);
+        assert_eq!(delist_edition_copy(second.token_id), Err(VibeError::NotVibeOwner));
+        let bought = buy_edition_copy(second.token_id).unwrap();
+        assert_eq!((bought.owner, bought.list_price), (bob, None));
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 40 - 50);
+        assert_eq!(buy_edition_copy(second.token_id).map(|_| ()), Err(VibeError::ListingNotFound));
+        set_caller(alice);
+        assert_eq!(get_my_balance(), alice_balance + 50 - 1 - 5);
+        set_caller(creator);
+        assert_eq!(get_my_balance(), creator_balance + 5);
+        assert_eq!(get_my_royalty_earnings().payments[0].vibe_id, vibe_id);
+
+        // Resetting the account burns the creator's other vibes but keeps the
+        // original behind the collected copies
+        let plain = mint_vibe("No editions here".to_string(), None).unwrap();
+        reset_account();
+        assert!(get_tombstone(plain).is_some());
+        assert!(get_tombstone(vibe_id.clone()).is_none());
+        assert_eq!(get_edition_info(vibe_id.clone()).map(|info| info.sold_out), Some(true));
+        assert_eq!(get_edition_copy(second.token_id).map(|copy| copy.owner), Some(bob));
+        assert_eq!(get_my_vibes().iter().map(|v| v.id.clone()).collect::<Vec<_>>(), vec![vibe_id]);
+    }
+
+    #[test]
+    fn test_remix_lineage() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let original_creator = Principal::from_slice(&[2; 29]);
+        let remixer = Principal::from_slice(&[3; 29]);
+        let fan = Principal::from_slice(&[4; 29]);
+
+        set_caller(admin);
+        init(None);
+        assert_eq!(set_remix_upstream_share(9_000), Err(VibeError::InvalidRemixShare { max_bps: 5_000 }));
+        assert_eq!(set_remix_upstream_share(5_000), Ok(()));
+
+        set_caller(original_creator);
+        let original = mint_vibe("The original".to_string(), None).unwrap();
+
+        set_caller(remixer);
+        let missing = MintOptions { remix_of: Some("missing".to_string()), ..Default::default() };
+        assert_eq!(mint_vibe("Remix of nothing".to_string(), Some(missing)), Err(VibeError::VibeNotFound));
+        let options = MintOptions { remix_of: Some(original.clone()), ..Default::default() };
+        let remix = mint_vibe("The remix".to_string(), Some(options)).unwrap();
+        let options = MintOptions { remix_of: Some(remix.clone()), ..Default::default() };
+        let remix_of_remix = mint_vibe("The remix of the remix".to_string(), Some(options)).unwrap();
+        let sibling_options = MintOptions { remix_of: Some(original.clone()), ..Default::default() };
+        set_mock_time(1_640_995_300);
+        let sibling = mint_vibe("Another take".to_string(), Some(sibling_options)).unwrap();
+
+        let lineage = get_lineage(remix_of_remix.clone()).unwrap();
+        assert_eq!(lineage.ancestors, vec![original.clone(), remix.clone()]);
+        assert_eq!(lineage.root.vibe_id, original);
+        let children: Vec<&str> = lineage.root.children.iter().map(|c| c.vibe_id.as_str()).collect();
+        assert_eq!(children, vec![remix.as_str(), sibling.as_str()]);
+        assert_eq!(lineage.root.children[0].children[0].vibe_id, remix_of_remix);
+        assert_eq!(lineage.root.children[0].children[0].vibe.as_ref().unwrap().parent_id, Some(remix.clone()));
+
+        // Half of the remix's like reward goes to the original's creator
+        set_caller(original_creator);
+        let original_balance = get_my_balance();
+        set_caller(remixer);
+        let remixer_balance = get_my_balance();
+        set_caller(fan);
+        assert_eq!(like_vibe(remix.clone()), Ok(1));
+        set_caller(original_creator);
+        let upstream = get_my_balance() - original_balance;
+        set_caller(remixer);
+        let kept = get_my_balance() - remixer_balance;
+        assert_eq!(upstream, kept);
+        assert!(upstream > 0);
+
+        // The original's creator hears about remixes
+        set_caller(original_creator);
+        let kinds: Vec<NotificationKind> = get_notifications(None, 10)
+            .notifications
+            .into_iter()
+            .map(|n| n.kind)
+            .collect();
+        assert!(kinds.contains(&NotificationKind::Remix { vibe_id: original, remix_id: remix }));
+    }
+
+    #[test]
+    fn test_collab_vibes() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let lead = Principal::from_slice(&[1; 29]);
+        let partner = Principal::from_slice(&[2; 29]);
+        let fan = Principal::from_slice(&[3; 29]);
+
+        let shares = vec![
+            CreatorShare { creator: lead, share_bps: 6_000 },
+            CreatorShare { creator: partner, share_bps: 4_000 },
+        ];
+
+        set_caller(lead);
+        let uneven = vec![
+            CreatorShare { creator: lead, share_bps: 6_000 },
+            CreatorShare { creator: partner, share_bps: 3_000 },
+        ];
+        assert_eq!(propose_collab_vibe("Duet".to_string(), uneven, None), Err(VibeError::InvalidCollabShares));
+
+        // A declined proposal refunds the mint cost
+        let declined = propose_collab_vibe("Duet".to_string(), shares.clone(), None).unwrap();
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - MINT_COST);
+        set_caller(partner);
+        assert_eq!(decline_collab(declined), Ok(()));
+        set_caller(lead);
+        assert_eq!(get_my_balance(), INITIAL_BALANCE);
+
+        // Nothing is published until every co-creator accepts
+        let proposal_id = propose_collab_vibe("Duet".to_string(), shares.clone(), None).unwrap();
+        assert!(get_my_vibes().is_empty());
+        set_caller(fan);
+        assert_eq!(accept_collab(proposal_id), Err(VibeError::NotCollaborator));
+        set_caller(partner);
+        assert_eq!(get_my_collab_proposals().len(), 1);
+        assert_eq!(accept_collab(proposal_id), Ok(CollabStatus::Published));
+        let vibe_id = get_collab_proposal(proposal_id).unwrap().vibe_id.unwrap();
+
+        set_caller(lead);
+        let vibe = get_my_vibes().pop().unwrap();
+        assert_eq!(vibe.id, vibe_id);
+        assert_eq!(vibe.collaborators, shares);
+        let lead_balance = get_my_balance();
+        set_caller(partner);
+        let partner_balance = get_my_balance();
+
+        // Tips and the first sale are split 60/40
+        set_caller(fan);
+        assert_eq!(tip_vibe(vibe_id.clone(), 50, None), Ok(50));
+        set_caller(lead);
+        assert_eq!(get_my_balance(), lead_balance + 30);
+        assert_eq!(list_vibe(vibe_id.clone(), 40), Ok(()));
+        set_caller(fan);
+        assert_eq!(buy_vibe(vibe_id).unwrap().fee, 1);
+        set_caller(partner);
+        assert_eq!(get_my_balance(), partner_balance + 20 + 15);
+        set_caller(lead);
+        assert_eq!(get_my_balance(), lead_balance + 30 + 24);
+    }
+
+    #[test]
+    fn test_collections() {
+        set_mock_time(1640995200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let other = Principal::from_slice(&[2; 29]);
+        let fan = Principal::from_slice(&[3; 29]);
+
+        set_caller(other);
+        let foreign = mint_vibe("Not yours".to_string(), None).unwrap();
+
+        set_caller(creator);
+        let first = mint_vibe("First track".to_string(), None).unwrap();
+        let second = mint_vibe("Second track".to_string(), None).unwrap();
+        let third = mint_vibe("Third track".to_string(), None).unwrap();
+
+        assert!(matches!(create_collection("  ".to_string(), String::new()), Err(VibeError::ContentTooShort { .. })));
+        let album = create_collection("Summer album".to_string(), String::new()).unwrap();
+        assert_eq!(add_to_collection(album, foreign.clone(), None), Err(VibeError::NotVibeOwner));
+        add_to_collection(album, first.clone(), None).unwrap();
+        add_to_collection(album, second.clone(), None).unwrap();
+        add_to_collection(album, third.clone(), Some(0)).unwrap();
+        assert_eq!(add_to_collection(album, first.clone(), None), Err(VibeError::AlreadyInCollection));
+        assert_eq!(
+            update_collection(album, "Summer album".to_string(), "Hot days".to_string(), Some(foreign.clone())),
+            Err(VibeError::NotInCollection)
+        );
+        update_collection(album, "Summer album".to_string(), "Hot days".to_string(), Some(second.clone())).unwrap();
+
+        assert_eq!(reorder_collection(album, vec![first.clone(), second.clone()]), Err(VibeError::NotInCollection));
+        reorder_collection(album, vec![second.clone(), first.clone(), third.clone()]).unwrap();
+
+        set_caller(other);
+        assert_eq!(remove_from_collection(album, first.clone()), Err(VibeError::NotCollectionOwner));
+
+        set_caller(fan);
+        like_vibe(first.clone()).unwrap();
+        like_vibe(second.clone()).unwrap();
+        share_vibe(second.clone()).unwrap();
+
+        let view = get_collection(album).unwrap();
+        let order: Vec<&str> = view.vibes.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(order, vec![second.as_str(), first.as_str(), third.as_str()]);
+        assert_eq!(view.stats, CollectionStats { vibes: 3, likes: 2, shares: 1 });
+
+        let metadata = get_collection_metadata(album).unwrap();
+        assert!(metadata.contains(&("icrc7:name".to_string(), MetadataValue::Text("Summer album".to_string()))));
+        assert!(metadata.contains(&("icrc7:total_supply".to_string(), MetadataValue::Nat(3u64.into()))));
+        assert!(metadata.contains(&("vibe:cover".to_string(), MetadataValue::Text(second.clone()))));
+
+        // Burning a vibe drops it from the collection and clears the cover
+        set_caller(creator);
+        burn_vibe(second.clone()).unwrap();
+        let view = get_collection(album).unwrap();
+        assert_eq!(view.collection.vibe_ids, vec![first.clone(), third.clone()]);
+        assert_eq!(view.collection.cover_vibe_id, None);
+        assert_eq!(view.stats, CollectionStats { vibes: 2, likes: 1, shares: 0 });
+
+        let collections = get_creator_collections(creator);
+        assert_eq!(collections.len(), 1);
+        assert_eq!(collections[0].0.id, album);
+        delete_collection(album).unwrap();
+        assert!(matches!(get_collection(album), Err(VibeError::CollectionNotFound)));
+        assert!(get_creator_collections(creator).is_empty());
+    }
+
+    #[test]
+    fn test_mint_pricing() {
+        set_mock_time(1_640_995_200);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let admin = Principal::from_slice(&[1; 29]);
+        let creator = Principal::from_slice(&[2; 29]);
+        let other = Principal::from_slice(&[3; 29]);
+
+        set_caller(admin);
+        init(None);
+        assert_eq!(get_mint_pricing(), MintPricing::Flat { price: MINT_COST });
+        assert_eq!(set_mint_pricing(MintPricing::Flat { price: 0 }), Err(VibeError::InvalidMintPricing));
+        assert_eq!(set_mint_pricing(MintPricing::Escalating { base: 5, step: 3 }), Ok(()));
+
+        set_caller(creator);
+        assert_eq!(
+            set_mint_pricing(MintPricing::Flat { price: 1 }),
+            Err(VibeError::Unauthorized { required: Role::Admin })
+        );
+        assert_eq!(quote_mint_cost(), 5);
+        let first = mint_vibe("First of the day".to_string(), None).unwrap();
+        assert_eq!(quote_mint_cost(), 8);
+        let capped = MintOptions { max_cost: Some(7), ..Default::default() };
+        assert_eq!(
+            mint_vibe("Too pricey".to_string(), Some(capped)),
+            Err(VibeError::MintCostExceeded { quoted: 8 })
+        );
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 5);
+        let capped = MintOptions { max_cost: Some(8), ..Default::default() };
+        let second = mint_vibe("Second of the day".to_string(), Some(capped)).unwrap();
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 13);
+
+        // Escalation is per creator and wears off after a day
+        set_caller(other);
+        assert_eq!(quote_mint_cost(), 5);
+        set_caller(creator);
+        assert_eq!(quote_mint_cost(), 11);
+
+        // A burn refunds what the vibe actually cost
+        assert_eq!(burn_vibe(second), Ok(8));
+        set_mock_time(1_640_995_200 + 24 * 60 * 60);
+        assert_eq!(quote_mint_cost(), 5);
+
+        // The bonding curve follows the number of live vibes on the platform
+        set_caller(admin);
+        let zero_spacing = MintPricing::BondingCurve { base: 2, step: 1, every: 0 };
+        assert_eq!(set_mint_pricing(zero_spacing), Err(VibeError::InvalidMintPricing));
+        assert_eq!(set_mint_pricing(MintPricing::BondingCurve { base: 2, step: 1, every: 2 }), Ok(()));
+        set_caller(other);
+        assert_eq!(quote_mint_cost(), 2);
+        mint_vibe("Bonding one".to_string(), None).unwrap();
+        assert_eq!(quote_mint_cost(), 3);
+        set_caller(creator);
+        burn_vibe(first).unwrap();
+        assert_eq!(quote_mint_cost(), 2);
+    }
+
+    #[test]
+    fn test_supply_accounting() {
+        set_mock_time(1_720_000_000);
+        STATE.with(|s| *s.borrow_mut() = State::default());
+
+        let creator = Principal::from_slice(&[1; 29]);
+        let fan = Principal::from_slice(&[2; 29]);
+        let bidder = Principal::from_slice(&[3; 29]);
+        let partner = Principal::from_slice(&[4; 29]);
+
+        let assert_balanced = || {
+            let audit = audit_supply();
+            assert!(audit.balanced, "{audit:?}");
+            assert_eq!(audit.total_supply, total_supply());
+            assert_eq!(audit.circulating_supply, circulating_supply());
+            assert_eq!(audit.treasury_balance, treasury_balance());
+            assert_eq!(audit.circulating_supply + audit.treasury_balance, audit.total_supply);
+        };
+
+        // Mint fees go to the treasury instead of vanishing
+        set_caller(creator);
+        let vibe = mint_vibe("Counting every token".to_string(), None).unwrap();
+        let auctioned = mint_vibe("Going once".to_string(), None).unwrap();
+        let fresh = mint_vibe("Burn me".to_string(), None).unwrap();
+        assert_eq!(treasury_balance(), TREASURY_RESERVE + 3 * MINT_COST);
+        assert_eq!(total_supply(), INITIAL_BALANCE + TREASURY_RESERVE);
+        assert_balanced();
+        assert_eq!(burn_vibe(fresh), Ok(MINT_COST));
+        assert_eq!(treasury_balance(), TREASURY_RESERVE + 2 * MINT_COST);
+        assert_balanced();
+
+        // Rewards are paid by the treasury
+        set_caller(fan);
+        like_vibe(vibe.clone()).unwrap();
+        share_vibe(vibe.clone()).unwrap();
+        add_comment(vibe.clone(), "Nice".to_string(), None).unwrap();
+        react_vibe(vibe.clone(), Reaction::Fire).unwrap();
+        tip_vibe(vibe.clone(), 7, None).unwrap();
+        assert_balanced();
+
+        // Held bids count towards supply while the auction is open
+        set_caller(creator);
+        let auction = create_auction(auctioned.clone(), AuctionKind::English, 10, 3_600).unwrap();
+        set_caller(bidder);
+        place_bid(auction, 20).unwrap();
+        assert_eq!(audit_supply().escrowed, 20);
+        assert_balanced();
+        set_mock_time(1_720_003_600);
+        assert_eq!(settle_auction(auction), Ok(AuctionStatus::Sold));
+        assert_eq!(audit_supply().escrowed, 0);
+        assert_balanced();
+
+        // Staked tokens sit in the treasury, which also pays staking rewards
+        set_caller(fan);
+        let treasury = treasury_balance();
+        stake_tokens(10);
+        assert_eq!(treasury_balance(), treasury + 10);
+        claim_staking_rewards();
+        assert_eq!(treasury_balance(), treasury + 5);
+        assert_balanced();
+
+        // A declined collab refunds the held mint price from the treasury
+        set_caller(creator);
+        let shares = vec![
+            CreatorShare { creator, share_bps: 5_000 },
+            CreatorShare { creator: partner, share_bps: 5_000 },
+        ];
+        let proposal = propose_collab_vibe("Two of us".to_string(), shares, None).unwrap();
+        set_caller(partner);
+        decline_collab(proposal).unwrap();
+        assert_balanced();
+
+        // Resetting an account moves the difference to or from the treasury
+        set_caller(creator);
+        assert!(get_my_balance() > INITIAL_BALANCE);
+        reset_account();
+        assert_eq!(get_my_balance(), INITIAL_BALANCE);
+        assert_balanced();
+
+        // A spent balance is refilled by the treasury once a day
+        set_caller(bidder);
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - 20);
+        reset_account();
+        assert_eq!(get_my_balance(), INITIAL_BALANCE);
+        mint_vibe("Spend it again".to_string(), None).unwrap();
+        reset_account();
+        assert_eq!(get_my_balance(), INITIAL_BALANCE - MINT_COST);
+        assert_balanced();
+        set_mock_time(1_720_003_600 + 24 * 60 * 60);
+        reset_account();
+        assert_eq!(get_my_balance(), INITIAL_BALANCE);
+        assert_balanced();
+
+        // Once the treasury runs dry, payouts stop instead of minting new tokens
+        set_caller(fan);
+        let last = mint_vibe("Nothing left to pay".to_string(), None).unwrap();
+        let supply = total_supply();
+        let reserve = treasury_balance();
+        STATE.with(|s| {
+            let mut state = s.borrow_mut();
+            treasury::withdraw(&mut state, reserve);
+            *state.token_balances.get_mut(&fan).unwrap() += reserve;
+        });
+        let fan_balance = get_my_balance();
+        set_caller(bidder);
+        like_vibe(last).unwrap();
+        assert_eq!(get_my_balance(), INITIAL_BALANCE);
+        set_caller(fan);
+        assert_eq!(get_my_balance(), fan_balance);
+        assert_eq!(claim_staking_rewards(), 0);
+        assert_eq!(total_supply(), supply);
+        assert_balanced();
+    }
 }
@@ -0,0 +1,267 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::HashMap;
+
+use crate::error::VibeError;
+use crate::{
+    collabs, current_caller, find_vibe, find_vibe_mut, get_timestamp, is_vibe_visible, rebuild_leaderboard, royalties,
+    treasury, State, Vibe, INITIAL_BALANCE, MAX_PAGE_SIZE, STATE,
+};
+
+// Platform fee on every sale, in basis points of the price
+const PLATFORM_FEE_BPS: u64 = 250;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Listing {
+    pub vibe_id: String,
+    pub seller: Principal,
+    pub price: u64,
+    pub listed_at: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Sale {
+    pub vibe_id: String,
+    pub seller: Principal,
+    pub buyer: Principal,
+    pub price: u64,
+    pub fee: u64,
+    pub royalty: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct ListingFilter {
+    pub seller: Option<Principal>,
+    pub creator: Option<Principal>,
+    pub tag: Option<String>,
+    pub min_price: Option<u64>,
+    pub max_price: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, CandidType, Deserialize)]
+pub enum ListingSort {
+    #[default]
+    Newest,
+    PriceAscending,
+    PriceDescending,
+    MostLiked,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct ListedVibe {
+    pub listing: Listing,
+    pub vibe: Vibe,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct ListingPage {
+    pub listings: Vec<ListedVibe>,
+    // Offset into the sorted listings for the next page
+    pub next_cursor: Option<u32>,
+}
+
+// A listed vibe is held in escrow: it can't be edited, burned, listed again or
+// auctioned until it is bought or delisted
+#[derive(Default, Clone)]
+pub(crate) struct MarketplaceState {
+    listings: HashMap<String, Listing>,
+    // Completed sales per vibe ID, oldest first
+    sales: HashMap<String, Vec<Sale>>,
+}
+
+impl MarketplaceState {
+    pub(crate) fn is_listed(&self, vibe_id: &str) -> bool {
+        self.listings.contains_key(vibe_id)
+    }
+}
+
+// Listed or auctioned vibes are held in escrow
+pub(crate) fn is_escrowed(state: &State, vibe_id: &str) -> bool {
+    state.marketplace.is_listed(vibe_id) || state.auctions.is_active(vibe_id)
+}
+
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    state.marketplace.listings.remove(vibe_id);
+}
+
+pub(crate) fn platform_fee(price: u64) -> u64 {
+    price * PLATFORM_FEE_BPS / 10_000
+}
+
+// Completes a sale the buyer has already paid for: the seller receives the
+// price less the platform fee, which goes to the treasury, and the creator's
+// royalty, and ownership moves
+pub(crate) fn settle_sale(state: &mut State, vibe_id: &str, seller: Principal, buyer: Principal, price: u64) -> Sale {
+    let fee = platform_fee(price);
+    let royalty = royalties::pay_royalty(state, vibe_id, seller, buyer, price);
+    // A first sale by the lead creator is split between all the co-creators
+    if find_vibe(state, vibe_id).is_some_and(|v| v.creator == seller) {
+        collabs::credit_creators(state, vibe_id, price - fee - royalty);
+    } else {
+        *state.token_balances.entry(seller).or_insert(INITIAL_BALANCE) += price - fee - royalty;
+    }
+    treasury::deposit(state, fee);
+
+    if let Some(vibe) = find_vibe_mut(state, vibe_id) {
+        vibe.owner = buyer;
+    }
+
+    let sale = Sale {
+        vibe_id: vibe_id.to_string(),
+        seller,
+        buyer,
+        price,
+        fee,
+        royalty,
+        timestamp: get_timestamp(),
+    };
+    state.marketplace.sales.entry(vibe_id.to_string()).or_default().push(sale.clone());
+    sale
+}
+
+#[update]
+pub(crate) fn list_vibe(vibe_id: String, price: u64) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+        let owner = find_vibe(&state, &vibe_id).map(|v| v.owner);
+        if owner != Some(user) {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if price == 0 {
+            return Err(VibeError::InvalidPrice);
+        }
+        if is_escrowed(&state, &vibe_id) {
+            return Err(VibeError::AlreadyListed);
+        }
+
+        state.marketplace.listings.insert(vibe_id.clone(), Listing {
+            vibe_id,
+            seller: user,
+            price,
+            listed_at: get_timestamp(),
+        });
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn delist_vibe(vibe_id: String) -> Result<(), VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let listing = state.marketplace.listings.get(&vibe_id).ok_or(VibeError::ListingNotFound)?;
+        if listing.seller != user {
+            return Err(VibeError::NotVibeOwner);
+        }
+        state.marketplace.listings.remove(&vibe_id);
+        Ok(())
+    })
+}
+
+// Pays the listed price from the buyer's balance
+#[update]
+pub(crate) fn buy_vibe(vibe_id: String) -> Result<Sale, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let listing = state.marketplace.listings.get(&vibe_id).cloned().ok_or(VibeError::ListingNotFound)?;
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+        if listing.seller == user {
+            return Err(VibeError::CannotBuyOwnVibe);
+        }
+        let balance = *state.token_balances.get(&user).unwrap_or(&INITIAL_BALANCE);
+        if balance < listing.price {
+            return Err(VibeError::InsufficientBalance);
+        }
+
+        state.token_balances.insert(user, balance - listing.price);
+        state.marketplace.listings.remove(&vibe_id);
+        let sale = settle_sale(&mut state, &vibe_id, listing.seller, user, listing.price);
+        rebuild_leaderboard(&mut state);
+
+        Ok(sale)
+    })
+}
+
+#[query]
+pub(crate) fn get_listings(filter: ListingFilter, sort: ListingSort, cursor: Option<u32>, limit: u32) -> ListingPage {
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+    let offset = cursor.unwrap_or(0) as usize;
+    let tag = filter.tag.map(|t| t.trim_start_matches('#').to_lowercase());
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut listings: Vec<ListedVibe> = state.marketplace.listings
+            .values()
+            .filter(|l| filter.seller.is_none_or(|s| s == l.seller))
+            .filter(|l| filter.min_price.is_none_or(|min| l.price >= min))
+            .filter(|l| filter.max_price.is_none_or(|max| l.price <= max))
+            .filter_map(|l| {
+                find_vibe(&state, &l.vibe_id)
+                    .filter(|_| is_vibe_visible(&state, &l.vibe_id))
+                    .map(|vibe| ListedVibe { listing: l.clone(), vibe: vibe.clone() })
+            })
+            .filter(|l| filter.creator.is_none_or(|c| c == l.vibe.creator))
+            .filter(|l| tag.as_ref().is_none_or(|t| l.vibe.tags.contains(t)))
+            .collect();
+
+        listings.sort_by(|a, b| {
+            let order = match sort {
+                ListingSort::Newest => b.listing.listed_at.cmp(&a.listing.listed_at),
+                ListingSort::PriceAscending => a.listing.price.cmp(&b.listing.price),
+                ListingSort::PriceDescending => b.listing.price.cmp(&a.listing.price),
+                ListingSort::MostLiked => b.vibe.likes.cmp(&a.vibe.likes),
+            };
+            order.then_with(|| a.vibe.id.cmp(&b.vibe.id))
+        });
+
+        let has_more = listings.len() > offset + limit;
+        let listings: Vec<ListedVibe> = listings.into_iter().skip(offset).take(limit).collect();
+        let next_cursor = has_more.then_some((offset + limit) as u32);
+
+        ListingPage { listings, next_cursor }
+    })
+}
+
+#[query]
+pub(crate) fn get_sale_history(vibe_id: String) -> Vec<Sale> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        state.marketplace.sales
+            .get(&vibe_id)
+            .cloned()
+            .unwrap_or_default()
+    })
+}
+
+// Vibes the caller currently owns, whether minted or bought
+#[query]
+pub(crate) fn get_my_owned_vibes() -> Vec<Vibe> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        let mut vibes: Vec<Vibe> = state.user_vibes
+            .values()
+            .flatten()
+            .filter(|v| v.owner == user)
+            .cloned()
+            .collect();
+        vibes.sort_by(|a, b| (b.timestamp, &b.id).cmp(&(a.timestamp, &a.id)));
+        vibes
+    })
+}
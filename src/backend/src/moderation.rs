@@ -0,0 +1,299 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::HashMap;
+
+use crate::access::{self, Role};
+use crate::error::VibeError;
+use crate::{current_caller, find_vibe, find_vibe_mut, get_timestamp, rebuild_leaderboard, State, Vibe, STATE};
+
+// Reports needed before a vibe is hidden automatically pending review
+const AUTO_HIDE_REPORT_THRESHOLD: usize = 5;
+const MAX_REASON_LENGTH: usize = 500;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, CandidType, Deserialize)]
+pub enum Visibility {
+    #[default]
+    Visible,
+    Hidden,
+    Removed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum ModerationAction {
+    Hide,
+    Remove,
+    Restore,
+    Dismiss,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum AppealStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Report {
+    reporter: Principal,
+    reason: String,
+    timestamp: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Warning {
+    vibe_id: String,
+    moderator: Principal,
+    message: String,
+    timestamp: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Appeal {
+    vibe_id: String,
+    creator: Principal,
+    statement: String,
+    timestamp: u64,
+    status: AppealStatus,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct QueueEntry {
+    vibe: Vibe,
+    reports: Vec<Report>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct ModerationState {
+    // Open reports per vibe ID, cleared once a moderator acts on the vibe
+    reports: HashMap<String, Vec<Report>>,
+    warnings: HashMap<Principal, Vec<Warning>>,
+    appeals: HashMap<String, Appeal>,
+}
+
+pub(crate) fn require_moderator(state: &State) -> Result<(), VibeError> {
+    access::require_role(state, &current_caller(), Role::Moderator)
+}
+
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    state.moderation.reports.remove(vibe_id);
+    state.moderation.appeals.remove(vibe_id);
+}
+
+fn validate_text(text: &str) -> Result<(), VibeError> {
+    let text = text.trim();
+    if text.is_empty() || text.chars().count() > MAX_REASON_LENGTH {
+        return Err(VibeError::InvalidReason);
+    }
+    Ok(())
+}
+
+#[update]
+pub(crate) fn report_vibe(vibe_id: String, reason: String) -> Result<u64, VibeError> {
+    let user = current_caller();
+    validate_text(&reason)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let vibe = find_vibe(&state, &vibe_id).ok_or(VibeError::VibeNotFound)?;
+        if vibe.creator == user {
+            return Err(VibeError::CannotReportOwnVibe);
+        }
+        if vibe.visibility == Visibility::Removed {
+            return Err(VibeError::VibeNotFound);
+        }
+
+        let reports = state.moderation.reports.entry(vibe_id.clone()).or_default();
+        if reports.iter().any(|r| r.reporter == user) {
+            return Err(VibeError::AlreadyReported);
+        }
+
+        reports.push(Report {
+            reporter: user,
+            reason: reason.trim().to_string(),
+            timestamp: get_timestamp(),
+        });
+        let report_count = reports.len();
+
+        if report_count >= AUTO_HIDE_REPORT_THRESHOLD {
+            if let Some(vibe) = find_vibe_mut(&mut state, &vibe_id) {
+                if vibe.visibility == Visibility::Visible {
+                    vibe.visibility = Visibility::Hidden;
+                }
+            }
+            rebuild_leaderboard(&mut state);
+        }
+
+        Ok(report_count as u64)
+    })
+}
+
+#[query]
+pub(crate) fn get_moderation_queue() -> Result<Vec<QueueEntry>, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        require_moderator(&state)?;
+
+        let mut queue: Vec<QueueEntry> = state.moderation.reports
+            .iter()
+            .filter(|(_, reports)| !reports.is_empty())
+            .filter_map(|(id, reports)| {
+                find_vibe(&state, id).map(|vibe| QueueEntry {
+                    vibe: vibe.clone(),
+                    reports: reports.clone(),
+                })
+            })
+            .collect();
+
+        // Most reported first, oldest vibe first among ties
+        queue.sort_by(|a, b| {
+            b.reports.len()
+                .cmp(&a.reports.len())
+                .then(a.vibe.timestamp.cmp(&b.vibe.timestamp))
+        });
+        Ok(queue)
+    })
+}
+
+#[update]
+pub(crate) fn moderate_vibe(vibe_id: String, action: ModerationAction) -> Result<(), VibeError> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        require_moderator(&state)?;
+
+        let vibe = find_vibe_mut(&mut state, &vibe_id).ok_or(VibeError::VibeNotFound)?;
+        match action {
+            ModerationAction::Hide => vibe.visibility = Visibility::Hidden,
+            ModerationAction::Remove => vibe.visibility = Visibility::Removed,
+            ModerationAction::Restore => vibe.visibility = Visibility::Visible,
+            ModerationAction::Dismiss => {}
+        }
+
+        // Acting on a vibe resolves every report filed against it
+        state.moderation.reports.remove(&vibe_id);
+
+        // A restore settles any appeal that is still waiting
+        if action == ModerationAction::Restore {
+            if let Some(appeal) = state.moderation.appeals.get_mut(&vibe_id) {
+                if appeal.status == AppealStatus::Pending {
+                    appeal.status = AppealStatus::Accepted;
+                }
+            }
+        }
+
+        rebuild_leaderboard(&mut state);
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn warn_creator(vibe_id: String, message: String) -> Result<(), VibeError> {
+    let moderator = current_caller();
+    validate_text(&message)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        require_moderator(&state)?;
+
+        let creator = find_vibe(&state, &vibe_id)
+            .map(|v| v.creator)
+            .ok_or(VibeError::VibeNotFound)?;
+
+        state.moderation.warnings.entry(creator).or_default().push(Warning {
+            vibe_id,
+            moderator,
+            message: message.trim().to_string(),
+            timestamp: get_timestamp(),
+        });
+        Ok(())
+    })
+}
+
+#[query]
+pub(crate) fn get_my_warnings() -> Vec<Warning> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        state.moderation.warnings
+            .get(&user)
+            .cloned()
+            .unwrap_or_default()
+    })
+}
+
+#[update]
+pub(crate) fn appeal_vibe(vibe_id: String, statement: String) -> Result<(), VibeError> {
+    let user = current_caller();
+    validate_text(&statement)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let vibe = find_vibe(&state, &vibe_id).ok_or(VibeError::VibeNotFound)?;
+        if vibe.creator != user {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if vibe.visibility == Visibility::Visible {
+            return Err(VibeError::NothingToAppeal);
+        }
+
+        let pending = state.moderation.appeals
+            .get(&vibe_id)
+            .map(|a| a.status == AppealStatus::Pending)
+            .unwrap_or(false);
+        if pending {
+            return Err(VibeError::AppealPending);
+        }
+
+        state.moderation.appeals.insert(vibe_id.clone(), Appeal {
+            vibe_id,
+            creator: user,
+            statement: statement.trim().to_string(),
+            timestamp: get_timestamp(),
+            status: AppealStatus::Pending,
+        });
+        Ok(())
+    })
+}
+
+#[query]
+pub(crate) fn get_pending_appeals() -> Result<Vec<Appeal>, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        require_moderator(&state)?;
+
+        let mut appeals: Vec<Appeal> = state.moderation.appeals
+            .values()
+            .filter(|a| a.status == AppealStatus::Pending)
+            .cloned()
+            .collect();
+        appeals.sort_by_key(|a| a.timestamp);
+        Ok(appeals)
+    })
+}
+
+#[update]
+pub(crate) fn resolve_appeal(vibe_id: String, accept: bool) -> Result<(), VibeError> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        require_moderator(&state)?;
+
+        let appeal = state.moderation.appeals
+            .get_mut(&vibe_id)
+            .filter(|a| a.status == AppealStatus::Pending)
+            .ok_or(VibeError::AppealNotFound)?;
+
+        if accept {
+            appeal.status = AppealStatus::Accepted;
+            if let Some(vibe) = find_vibe_mut(&mut state, &vibe_id) {
+                vibe.visibility = Visibility::Visible;
+            }
+            rebuild_leaderboard(&mut state);
+        } else {
+            appeal.status = AppealStatus::Rejected;
+        }
+        Ok(())
+    })
+}
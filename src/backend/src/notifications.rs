@@ -0,0 +1,188 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{HashMap, VecDeque};
+
+use crate::reactions::Reaction;
+use crate::{current_caller, get_timestamp, State, MAX_PAGE_SIZE, STATE};
+
+// Oldest notifications are dropped once an inbox reaches this size
+const MAX_INBOX_SIZE: usize = 500;
+const MAX_MENTIONS_PER_TEXT: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub enum NotificationKind {
+    Mention { vibe_id: String, comment_id: Option<u64> },
+    Like { vibe_id: String },
+    Share { vibe_id: String },
+    Reaction { vibe_id: String, reaction: Reaction },
+    Comment { vibe_id: String, comment_id: u64 },
+    Follow,
+    Outbid { auction_id: u64 },
+    Tip { vibe_id: String, amount: u64 },
+    Remix { vibe_id: String, remix_id: String },
+    CollabInvite { proposal_id: u64 },
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Notification {
+    pub id: u64,
+    pub kind: NotificationKind,
+    pub actor: Principal,
+    pub timestamp: u64,
+    pub read: bool,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct NotificationPage {
+    pub notifications: Vec<Notification>,
+    pub unread: u64,
+    pub next_cursor: Option<u64>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct NotificationState {
+    next_id: u64,
+    // Newest at the back
+    inboxes: HashMap<Principal, VecDeque<Notification>>,
+}
+
+// Queues a notification for `recipient` unless they caused it themselves
+pub(crate) fn notify(state: &mut State, recipient: Principal, actor: Principal, kind: NotificationKind) {
+    if recipient == actor {
+        return;
+    }
+
+    let id = state.notifications.next_id;
+    state.notifications.next_id += 1;
+
+    let inbox = state.notifications.inboxes.entry(recipient).or_default();
+    inbox.push_back(Notification {
+        id,
+        kind,
+        actor,
+        timestamp: get_timestamp(),
+        read: false,
+    });
+    if inbox.len() > MAX_INBOX_SIZE {
+        inbox.pop_front();
+    }
+}
+
+// Resolves `@handle` and `@<principal text>` mentions to principals. Unknown
+// handles and malformed principals are skipped
+pub(crate) fn parse_mentions(state: &State, text: &str) -> Vec<Principal> {
+    let mut mentioned: Vec<Principal> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let at_boundary = prev.is_none_or(|p| !p.is_alphanumeric() && p != '_' && p != '@');
+        prev = Some(c);
+        if c != '@' || !at_boundary {
+            continue;
+        }
+
+        let mut token = String::new();
+        while let Some(&next) = chars.peek() {
+            if !next.is_ascii_alphanumeric() && next != '_' && next != '-' {
+                break;
+            }
+            token.push(next);
+            prev = Some(next);
+            chars.next();
+        }
+
+        let principal = state.profiles
+            .handle_owner(&token)
+            .or_else(|| Principal::from_text(&token).ok());
+        if let Some(principal) = principal {
+            if !mentioned.contains(&principal) {
+                mentioned.push(principal);
+                if mentioned.len() == MAX_MENTIONS_PER_TEXT {
+                    break;
+                }
+            }
+        }
+    }
+
+    mentioned
+}
+
+pub(crate) fn notify_mentions(
+    state: &mut State,
+    actor: Principal,
+    text: &str,
+    vibe_id: &str,
+    comment_id: Option<u64>,
+) {
+    for principal in parse_mentions(state, text) {
+        let kind = NotificationKind::Mention {
+            vibe_id: vibe_id.to_string(),
+            comment_id,
+        };
+        notify(state, principal, actor, kind);
+    }
+}
+
+// Newest first; `cursor` is the ID of the last notification already seen
+#[query]
+pub(crate) fn get_notifications(cursor: Option<u64>, limit: u32) -> NotificationPage {
+    let user = current_caller();
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        let Some(inbox) = state.notifications.inboxes.get(&user) else {
+            return NotificationPage { notifications: Vec::new(), unread: 0, next_cursor: None };
+        };
+
+        let mut matching = inbox
+            .iter()
+            .rev()
+            .filter(|n| cursor.is_none_or(|c| n.id < c));
+        let notifications: Vec<Notification> = matching.by_ref().take(limit).cloned().collect();
+        let next_cursor = matching.next().and(notifications.last().map(|n| n.id));
+
+        NotificationPage {
+            notifications,
+            unread: inbox.iter().filter(|n| !n.read).count() as u64,
+            next_cursor,
+        }
+    })
+}
+
+#[query]
+pub(crate) fn get_unread_count() -> u64 {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        let unread = state.notifications.inboxes
+            .get(&user)
+            .map(|inbox| inbox.iter().filter(|n| !n.read).count() as u64)
+            .unwrap_or(0);
+        unread
+    })
+}
+
+// Marks notifications up to and including `up_to` as read, or all of them
+#[update]
+pub(crate) fn mark_notifications_read(up_to: Option<u64>) -> u64 {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let Some(inbox) = state.notifications.inboxes.get_mut(&user) else {
+            return 0;
+        };
+
+        let mut marked = 0;
+        for notification in inbox.iter_mut() {
+            if !notification.read && up_to.is_none_or(|id| notification.id <= id) {
+                notification.read = true;
+                marked += 1;
+            }
+        }
+        marked
+    })
+}
@@ -0,0 +1,130 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{HashMap, VecDeque};
+
+use crate::access;
+use crate::error::VibeError;
+use crate::{current_caller, get_timestamp, State, MINT_COST, STATE};
+
+// Window the escalating strategy counts a creator's recent mints over
+const ESCALATION_WINDOW_SECS: u64 = 24 * 60 * 60;
+// No strategy quotes more than this, however far the curve has climbed
+const MAX_MINT_PRICE: u64 = 1_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CandidType, Deserialize)]
+pub enum MintPricing {
+    // The same price for every mint
+    Flat { price: u64 },
+    // `base`, plus `step` for every vibe the caller minted in the last day
+    Escalating { base: u64, step: u64 },
+    // `base`, plus `step` for every `every` vibes live on the platform, so
+    // burns bring the price back down
+    BondingCurve { base: u64, step: u64, every: u64 },
+}
+
+impl Default for MintPricing {
+    fn default() -> Self {
+        MintPricing::Flat { price: MINT_COST }
+    }
+}
+
+impl MintPricing {
+    fn is_valid(&self) -> bool {
+        match *self {
+            MintPricing::Flat { price } => (1..=MAX_MINT_PRICE).contains(&price),
+            MintPricing::Escalating { base, .. } => (1..=MAX_MINT_PRICE).contains(&base),
+            MintPricing::BondingCurve { base, every, .. } => (1..=MAX_MINT_PRICE).contains(&base) && every > 0,
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct PricingState {
+    strategy: MintPricing,
+    // Each creator's mint times within the escalation window, oldest first
+    recent_mints: HashMap<Principal, VecDeque<u64>>,
+    // What each live vibe cost to mint, refunded if it is burned in time
+    paid: HashMap<String, u64>,
+}
+
+impl PricingState {
+    fn recent_mint_count(&self, user: Principal, now: u64) -> u64 {
+        self.recent_mints
+            .get(&user)
+            .map(|times| times.iter().filter(|t| now.saturating_sub(**t) < ESCALATION_WINDOW_SECS).count() as u64)
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn paid_for(&self, vibe_id: &str) -> u64 {
+        self.paid.get(vibe_id).copied().unwrap_or(MINT_COST)
+    }
+}
+
+fn live_vibes(state: &State) -> u64 {
+    state.user_vibes.values().map(|vibes| vibes.len() as u64).sum()
+}
+
+// What `user` would pay to mint a vibe at `now`
+pub(crate) fn quote(state: &State, user: Principal, now: u64) -> u64 {
+    let price = match state.pricing.strategy {
+        MintPricing::Flat { price } => price,
+        MintPricing::Escalating { base, step } => {
+            base.saturating_add(step.saturating_mul(state.pricing.recent_mint_count(user, now)))
+        }
+        MintPricing::BondingCurve { base, step, every } => {
+            base.saturating_add(step.saturating_mul(live_vibes(state) / every))
+        }
+    };
+    price.min(MAX_MINT_PRICE)
+}
+
+// Quotes the mint and checks it against the caller's slippage limit
+pub(crate) fn checked_quote(state: &State, user: Principal, now: u64, max_cost: Option<u64>) -> Result<u64, VibeError> {
+    let price = quote(state, user, now);
+    if max_cost.is_some_and(|max| price > max) {
+        return Err(VibeError::MintCostExceeded { quoted: price });
+    }
+    Ok(price)
+}
+
+// Counts a charged mint towards the creator's escalation
+pub(crate) fn record_mint(state: &mut State, user: Principal, now: u64) {
+    let times = state.pricing.recent_mints.entry(user).or_default();
+    while times.front().is_some_and(|t| now.saturating_sub(*t) >= ESCALATION_WINDOW_SECS) {
+        times.pop_front();
+    }
+    times.push_back(now);
+}
+
+pub(crate) fn record_paid(state: &mut State, vibe_id: &str, price: u64) {
+    state.pricing.paid.insert(vibe_id.to_string(), price);
+}
+
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    state.pricing.paid.remove(vibe_id);
+}
+
+#[query]
+pub(crate) fn quote_mint_cost() -> u64 {
+    let user = current_caller();
+    STATE.with(|state| quote(&state.borrow(), user, get_timestamp()))
+}
+
+#[query]
+pub(crate) fn get_mint_pricing() -> MintPricing {
+    STATE.with(|state| state.borrow().pricing.strategy)
+}
+
+#[update]
+pub(crate) fn set_mint_pricing(pricing: MintPricing) -> Result<(), VibeError> {
+    if !pricing.is_valid() {
+        return Err(VibeError::InvalidMintPricing);
+    }
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        access::require_admin(&state)?;
+        state.pricing.strategy = pricing;
+        Ok(())
+    })
+}
@@ -0,0 +1,256 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{HashMap, HashSet};
+
+use crate::access;
+use crate::error::VibeError;
+use crate::validation::sanitize;
+use crate::{current_caller, get_timestamp, State, STATE};
+
+const MIN_HANDLE_LENGTH: usize = 3;
+const MAX_HANDLE_LENGTH: usize = 20;
+const MAX_DISPLAY_NAME_LENGTH: usize = 50;
+const MAX_BIO_LENGTH: usize = 280;
+const MAX_AVATAR_LENGTH: usize = 256;
+const MAX_LINKS: usize = 5;
+const MAX_LINK_LENGTH: usize = 200;
+
+// Handles nobody can claim, on top of the ones admins reserve at runtime
+const RESERVED_HANDLES: [&str; 8] = [
+    "admin", "administrator", "moderator", "mod", "support", "system", "vibe", "vibes",
+];
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Profile {
+    pub principal: Principal,
+    pub handle: Option<String>,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    // URL or asset key of the avatar image
+    pub avatar: Option<String>,
+    pub links: Vec<String>,
+    pub updated_at: u64,
+}
+
+#[derive(Clone, Debug, Default, CandidType, Deserialize)]
+pub struct ProfileUpdate {
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar: Option<String>,
+    pub links: Vec<String>,
+}
+
+// Compact profile data attached to principals in other responses
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct ProfileSummary {
+    pub principal: Principal,
+    pub handle: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar: Option<String>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct ProfileState {
+    profiles: HashMap<Principal, Profile>,
+    // Lowercased handle to owner
+    handles: HashMap<String, Principal>,
+    reserved: HashSet<String>,
+}
+
+impl ProfileState {
+    pub(crate) fn handle_owner(&self, handle: &str) -> Option<Principal> {
+        self.handles.get(&handle.to_lowercase()).copied()
+    }
+
+    pub(crate) fn summary(&self, principal: Principal) -> ProfileSummary {
+        let profile = self.profiles.get(&principal);
+        ProfileSummary {
+            principal,
+            handle: profile.and_then(|p| p.handle.clone()),
+            display_name: profile.and_then(|p| p.display_name.clone()),
+            avatar: profile.and_then(|p| p.avatar.clone()),
+        }
+    }
+}
+
+fn empty_profile(principal: Principal) -> Profile {
+    Profile {
+        principal,
+        handle: None,
+        display_name: None,
+        bio: None,
+        avatar: None,
+        links: Vec::new(),
+        updated_at: 0,
+    }
+}
+
+// Handles are 3-20 characters of lowercase letters, digits and underscores,
+// starting with a letter
+pub(crate) fn normalize_handle(handle: &str) -> Result<String, VibeError> {
+    let handle = handle.trim().trim_start_matches('@').to_lowercase();
+    let valid = (MIN_HANDLE_LENGTH..=MAX_HANDLE_LENGTH).contains(&handle.len())
+        && handle.starts_with(|c: char| c.is_ascii_lowercase())
+        && handle.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if valid {
+        Ok(handle)
+    } else {
+        Err(VibeError::InvalidHandle)
+    }
+}
+
+fn is_reserved(state: &State, handle: &str) -> bool {
+    RESERVED_HANDLES.contains(&handle) || state.profiles.reserved.contains(handle)
+}
+
+// Sanitizes an optional free-text field, treating blank input as unset
+fn clean_field(value: Option<String>, max_length: usize, field: &str) -> Result<Option<String>, VibeError> {
+    let Some(value) = value.map(|v| sanitize(&v)).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    if value.chars().count() > max_length {
+        return Err(VibeError::InvalidProfileField { field: field.to_string() });
+    }
+    Ok(Some(value))
+}
+
+fn clean_links(links: Vec<String>) -> Result<Vec<String>, VibeError> {
+    if links.len() > MAX_LINKS {
+        return Err(VibeError::InvalidProfileField { field: "links".to_string() });
+    }
+
+    links.into_iter()
+        .map(|link| {
+            let link = link.trim().to_string();
+            if link.len() > MAX_LINK_LENGTH || !link.starts_with("https://") || link.contains(char::is_whitespace) {
+                return Err(VibeError::InvalidProfileField { field: "links".to_string() });
+            }
+            Ok(link)
+        })
+        .collect()
+}
+
+#[update]
+pub(crate) fn set_handle(handle: String) -> Result<String, VibeError> {
+    let user = current_caller();
+    if user == Principal::anonymous() {
+        return Err(VibeError::AnonymousCaller);
+    }
+    let handle = normalize_handle(&handle)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        match state.profiles.handles.get(&handle) {
+            Some(owner) if *owner == user => return Ok(handle),
+            Some(_) => return Err(VibeError::HandleTaken),
+            None => {}
+        }
+        if is_reserved(&state, &handle) {
+            return Err(VibeError::HandleReserved);
+        }
+
+        let now = get_timestamp();
+        let profile = state.profiles.profiles
+            .entry(user)
+            .or_insert_with(|| empty_profile(user));
+        let previous = profile.handle.replace(handle.clone());
+        profile.updated_at = now;
+
+        if let Some(previous) = previous {
+            state.profiles.handles.remove(&previous);
+        }
+        state.profiles.handles.insert(handle.clone(), user);
+        Ok(handle)
+    })
+}
+
+#[update]
+pub(crate) fn update_profile(update: ProfileUpdate) -> Result<Profile, VibeError> {
+    let user = current_caller();
+    if user == Principal::anonymous() {
+        return Err(VibeError::AnonymousCaller);
+    }
+
+    let display_name = clean_field(update.display_name, MAX_DISPLAY_NAME_LENGTH, "display_name")?;
+    let bio = clean_field(update.bio, MAX_BIO_LENGTH, "bio")?;
+    let avatar = clean_field(update.avatar, MAX_AVATAR_LENGTH, "avatar")?;
+    let links = clean_links(update.links)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let now = get_timestamp();
+
+        let profile = state.profiles.profiles
+            .entry(user)
+            .or_insert_with(|| empty_profile(user));
+        profile.display_name = display_name;
+        profile.bio = bio;
+        profile.avatar = avatar;
+        profile.links = links;
+        profile.updated_at = now;
+
+        Ok(profile.clone())
+    })
+}
+
+#[query]
+pub(crate) fn get_profile(principal: Principal) -> Option<Profile> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        state.profiles.profiles.get(&principal).cloned()
+    })
+}
+
+#[query]
+pub(crate) fn get_profile_by_handle(handle: String) -> Option<Profile> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        let profile = state.profiles
+            .handle_owner(handle.trim().trim_start_matches('@'))
+            .and_then(|owner| state.profiles.profiles.get(&owner))
+            .cloned();
+        profile
+    })
+}
+
+#[query]
+pub(crate) fn is_handle_available(handle: String) -> bool {
+    let Ok(handle) = normalize_handle(&handle) else {
+        return false;
+    };
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        !state.profiles.handles.contains_key(&handle) && !is_reserved(&state, &handle)
+    })
+}
+
+#[update]
+pub(crate) fn reserve_handle(handle: String) -> Result<(), VibeError> {
+    let handle = normalize_handle(&handle)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        access::require_admin(&state)?;
+
+        if state.profiles.handles.contains_key(&handle) {
+            return Err(VibeError::HandleTaken);
+        }
+        state.profiles.reserved.insert(handle);
+        Ok(())
+    })
+}
+
+#[update]
+pub(crate) fn release_handle(handle: String) -> Result<(), VibeError> {
+    let handle = normalize_handle(&handle)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        access::require_admin(&state)?;
+        state.profiles.reserved.remove(&handle);
+        Ok(())
+    })
+}
@@ -0,0 +1,178 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::HashMap;
+
+use crate::access;
+use crate::error::VibeError;
+use crate::{current_caller, get_timestamp, STATE};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, CandidType, Deserialize)]
+pub enum RateLimitedAction {
+    Mint,
+    Like,
+    Share,
+    Comment,
+    React,
+    // Treasury top-ups when resetting an account
+    Refill,
+}
+
+impl RateLimitedAction {
+    const ALL: [RateLimitedAction; 6] = [
+        RateLimitedAction::Mint,
+        RateLimitedAction::Like,
+        RateLimitedAction::Share,
+        RateLimitedAction::Comment,
+        RateLimitedAction::React,
+        RateLimitedAction::Refill,
+    ];
+
+    fn default_limit(self) -> RateLimit {
+        match self {
+            RateLimitedAction::Mint => RateLimit { capacity: 10, refill_secs: 60 },
+            RateLimitedAction::Like => RateLimit { capacity: 30, refill_secs: 10 },
+            RateLimitedAction::Share => RateLimit { capacity: 20, refill_secs: 15 },
+            RateLimitedAction::Comment => RateLimit { capacity: 20, refill_secs: 30 },
+            RateLimitedAction::React => RateLimit { capacity: 30, refill_secs: 10 },
+            RateLimitedAction::Refill => RateLimit { capacity: 1, refill_secs: 24 * 60 * 60 },
+        }
+    }
+}
+
+// A bucket holds up to `capacity` tokens and regains one every `refill_secs`
+#[derive(Clone, Copy, Debug, PartialEq, CandidType, Deserialize)]
+pub struct RateLimit {
+    pub capacity: u32,
+    pub refill_secs: u64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: u32,
+    last_refill: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Quota {
+    pub action: RateLimitedAction,
+    pub remaining: u32,
+    pub capacity: u32,
+    // Seconds until the next token is added, 0 when the bucket is full
+    pub next_refill_in: u64,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct RateLimitState {
+    // Overrides of the per-action defaults set by admins
+    limits: HashMap<RateLimitedAction, RateLimit>,
+    buckets: HashMap<(Principal, RateLimitedAction), Bucket>,
+}
+
+impl RateLimitState {
+    fn limit(&self, action: RateLimitedAction) -> RateLimit {
+        self.limits
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_limit())
+    }
+
+    // Returns the bucket as it stands at `now`, without storing it
+    fn refilled(&self, user: Principal, action: RateLimitedAction, now: u64) -> Bucket {
+        let limit = self.limit(action);
+        let Some(bucket) = self.buckets.get(&(user, action)) else {
+            return Bucket { tokens: limit.capacity, last_refill: now };
+        };
+
+        let elapsed = now.saturating_sub(bucket.last_refill);
+        let earned = elapsed / limit.refill_secs;
+        let tokens = (bucket.tokens as u64 + earned).min(limit.capacity as u64) as u32;
+        let last_refill = if tokens == limit.capacity {
+            now
+        } else {
+            bucket.last_refill + earned * limit.refill_secs
+        };
+
+        Bucket { tokens, last_refill }
+    }
+
+    // Takes a token for `action`, or reports how long until one is available
+    pub(crate) fn consume(
+        &mut self,
+        user: Principal,
+        action: RateLimitedAction,
+        now: u64,
+    ) -> Result<(), VibeError> {
+        let mut bucket = self.refilled(user, action, now);
+        if bucket.tokens == 0 {
+            let retry_after_secs = (bucket.last_refill + self.limit(action).refill_secs).saturating_sub(now);
+            return Err(VibeError::RateLimited { action, retry_after_secs });
+        }
+
+        bucket.tokens -= 1;
+        self.buckets.insert((user, action), bucket);
+        Ok(())
+    }
+
+    fn quota(&self, user: Principal, action: RateLimitedAction, now: u64) -> Quota {
+        let limit = self.limit(action);
+        let bucket = self.refilled(user, action, now);
+        let next_refill_in = if bucket.tokens >= limit.capacity {
+            0
+        } else {
+            (bucket.last_refill + limit.refill_secs).saturating_sub(now)
+        };
+
+        Quota {
+            action,
+            remaining: bucket.tokens,
+            capacity: limit.capacity,
+            next_refill_in,
+        }
+    }
+}
+
+#[query]
+pub(crate) fn get_my_quota() -> Vec<Quota> {
+    let user = current_caller();
+    let now = get_timestamp();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        RateLimitedAction::ALL
+            .iter()
+            .map(|action| state.rate_limits.quota(user, *action, now))
+            .collect()
+    })
+}
+
+#[query]
+pub(crate) fn get_rate_limits() -> Vec<(RateLimitedAction, RateLimit)> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        RateLimitedAction::ALL
+            .iter()
+            .map(|action| (*action, state.rate_limits.limit(*action)))
+            .collect()
+    })
+}
+
+#[update]
+pub(crate) fn set_rate_limit(action: RateLimitedAction, limit: RateLimit) -> Result<(), VibeError> {
+    if limit.capacity == 0 || limit.refill_secs == 0 {
+        return Err(VibeError::InvalidRateLimit);
+    }
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        access::require_admin(&state)?;
+
+        state.rate_limits.limits.insert(action, limit);
+        // Clamp existing buckets so a lowered capacity applies immediately
+        for ((_, bucket_action), bucket) in state.rate_limits.buckets.iter_mut() {
+            if *bucket_action == action {
+                bucket.tokens = bucket.tokens.min(limit.capacity);
+            }
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,217 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::error::VibeError;
+use crate::notifications::{self, NotificationKind};
+use crate::rate_limit::RateLimitedAction;
+use crate::{
+    collabs, collusion, current_caller, find_vibe, get_timestamp, is_vibe_visible, treasury, State,
+    LIKE_REWARD_CREATOR, LIKE_REWARD_USER, STATE,
+};
+
+// Reactions sit alongside likes; `like_vibe` and `Vibe.likes` are unchanged
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, CandidType, Deserialize)]
+pub enum Reaction {
+    Fire,
+    Love,
+    Laugh,
+    Wow,
+    Clap,
+    Sad,
+}
+
+impl Reaction {
+    // Creator reward multiplier relative to a like
+    fn reward_weight(self) -> f32 {
+        match self {
+            Reaction::Fire | Reaction::Love => 1.5,
+            Reaction::Laugh | Reaction::Wow | Reaction::Clap => 1.0,
+            Reaction::Sad => 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct ReactionCount {
+    pub reaction: Reaction,
+    pub count: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct ReactionSummary {
+    pub likes: u64,
+    pub reactions: Vec<ReactionCount>,
+    // The caller's own reactions on the vibe
+    pub mine: Vec<Reaction>,
+}
+
+// Per-user maps are keyed by vibe first so a burn drops them in one step
+#[derive(Default, Clone)]
+pub(crate) struct ReactionState {
+    counts: HashMap<String, BTreeMap<Reaction, u64>>,
+    by_user: HashMap<String, HashMap<Principal, BTreeSet<Reaction>>>,
+    // Reactions that already paid out, so removing and re-adding one earns nothing
+    rewarded: HashMap<String, HashMap<Principal, BTreeSet<Reaction>>>,
+}
+
+impl ReactionState {
+    fn mine(&self, user: Principal, vibe_id: &str) -> Option<&BTreeSet<Reaction>> {
+        self.by_user.get(vibe_id).and_then(|users| users.get(&user))
+    }
+
+    // Whether `user` has already been paid for reacting to the vibe
+    pub(crate) fn has_paid(&self, user: Principal, vibe_id: &str) -> bool {
+        self.rewarded.get(vibe_id).is_some_and(|users| users.contains_key(&user))
+    }
+
+    // Whether anyone besides the creator has reacted to the vibe, even if they
+    // have taken the reaction back since
+    pub(crate) fn has_reactions(&self, vibe_id: &str) -> bool {
+        self.rewarded.get(vibe_id).is_some_and(|users| !users.is_empty())
+    }
+}
+
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    state.reactions.counts.remove(vibe_id);
+    state.reactions.by_user.remove(vibe_id);
+    state.reactions.rewarded.remove(vibe_id);
+}
+
+// The reacting user is paid once per vibe, and not at all if they were already
+// paid for liking it, while the creator earns a weighted reward for every
+// distinct reaction
+fn reward_reaction(state: &mut State, user: Principal, owner: Principal, vibe_id: &str, reaction: Reaction) {
+    if user == owner {
+        return;
+    }
+    let user_paid = state.reactions.has_paid(user, vibe_id)
+        || state.user_likes.get(&user).is_some_and(|likes| likes.contains(vibe_id));
+    let newly_rewarded = state.reactions.rewarded
+        .entry(vibe_id.to_string())
+        .or_default()
+        .entry(user)
+        .or_default()
+        .insert(reaction);
+    if !newly_rewarded {
+        return;
+    }
+
+    let factor = collusion::check_engagement(state, user, owner);
+    let reputation = *state.reputation.get(&owner).unwrap_or(&1.0);
+    let creator_reward = (LIKE_REWARD_CREATOR as f32 * reaction.reward_weight() * reputation * factor) as u64;
+    let user_reward = if user_paid {
+        0
+    } else {
+        (LIKE_REWARD_USER as f32 * factor) as u64
+    };
+
+    let creator_reward = treasury::withdraw(state, creator_reward);
+    let paid: u64 = collabs::credit_creators(state, vibe_id, creator_reward)
+        .iter()
+        .map(|(_, part)| part)
+        .sum();
+    treasury::deposit(state, creator_reward - paid);
+    treasury::pay(state, user, user_reward);
+    *state.reputation.entry(owner).or_insert(1.0) += 0.02 * reaction.reward_weight() * factor;
+}
+
+#[update]
+pub(crate) fn react_vibe(vibe_id: String, reaction: Reaction) -> Result<u64, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let owner = find_vibe(&state, &vibe_id)
+            .map(|v| v.creator)
+            .ok_or(VibeError::VibeNotFound)?;
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+
+        if state.reactions.mine(user, &vibe_id).is_some_and(|r| r.contains(&reaction)) {
+            return Err(VibeError::AlreadyReacted);
+        }
+        state.rate_limits.consume(user, RateLimitedAction::React, get_timestamp())?;
+
+        state.reactions.by_user
+            .entry(vibe_id.clone())
+            .or_default()
+            .entry(user)
+            .or_default()
+            .insert(reaction);
+        let count = state.reactions.counts
+            .entry(vibe_id.clone())
+            .or_default()
+            .entry(reaction)
+            .or_insert(0);
+        *count += 1;
+        let count = *count;
+
+        reward_reaction(&mut state, user, owner, &vibe_id, reaction);
+        notifications::notify(&mut state, owner, user, NotificationKind::Reaction { vibe_id, reaction });
+
+        Ok(count)
+    })
+}
+
+#[update]
+pub(crate) fn unreact_vibe(vibe_id: String, reaction: Reaction) -> Result<u64, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let Some(users) = state.reactions.by_user.get_mut(&vibe_id) else {
+            return Err(VibeError::ReactionNotFound);
+        };
+        if !users.get_mut(&user).is_some_and(|r| r.remove(&reaction)) {
+            return Err(VibeError::ReactionNotFound);
+        }
+        if users.get(&user).is_some_and(|r| r.is_empty()) {
+            users.remove(&user);
+        }
+        if users.is_empty() {
+            state.reactions.by_user.remove(&vibe_id);
+        }
+
+        let counts = state.reactions.counts.entry(vibe_id).or_default();
+        let count = counts.entry(reaction).or_insert(0);
+        *count = count.saturating_sub(1);
+        let count = *count;
+        if count == 0 {
+            counts.remove(&reaction);
+        }
+
+        Ok(count)
+    })
+}
+
+#[query]
+pub(crate) fn get_reactions(vibe_id: String) -> ReactionSummary {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let likes = state.vibe_interactions
+            .get(&vibe_id)
+            .map(|stats| stats.likes)
+            .unwrap_or(0);
+        let reactions = state.reactions.counts
+            .get(&vibe_id)
+            .map(|counts| {
+                counts.iter()
+                    .map(|(reaction, count)| ReactionCount { reaction: *reaction, count: *count })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mine = state.reactions
+            .mine(user, &vibe_id)
+            .map(|r| r.iter().copied().collect())
+            .unwrap_or_default();
+
+        ReactionSummary { likes, reactions, mine }
+    })
+}
@@ -0,0 +1,158 @@
+use candid::{CandidType, Deserialize};
+use ic_cdk::{query, update};
+use std::collections::HashMap;
+
+use crate::access;
+use crate::error::VibeError;
+use crate::{collabs, find_vibe, is_vibe_visible, State, Vibe, STATE};
+
+// Share of a remix's like and share rewards passed to its parent, in basis
+// points, until an admin sets another
+const DEFAULT_UPSTREAM_SHARE_BPS: u16 = 2_000;
+const MAX_UPSTREAM_SHARE_BPS: u16 = 5_000;
+// Bounds on how far lineage walks and trees go
+const MAX_LINEAGE_DEPTH: usize = 16;
+const MAX_LINEAGE_NODES: usize = 500;
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct LineageNode {
+    pub vibe_id: String,
+    // None once the vibe has been burned or while moderation hides it
+    pub vibe: Option<Vibe>,
+    pub children: Vec<LineageNode>,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct Lineage {
+    // From the original down to the requested vibe's parent
+    pub ancestors: Vec<String>,
+    // The requested vibe's original, with every remix below it
+    pub root: LineageNode,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct RemixState {
+    // Lineage outlives burns, so it is kept here rather than read off `Vibe`
+    parents: HashMap<String, String>,
+    children: HashMap<String, Vec<String>>,
+    upstream_share_bps: Option<u16>,
+}
+
+impl RemixState {
+    fn upstream_share_bps(&self) -> u64 {
+        self.upstream_share_bps.unwrap_or(DEFAULT_UPSTREAM_SHARE_BPS) as u64
+    }
+
+    // Nearest first
+    fn ancestors(&self, vibe_id: &str) -> Vec<String> {
+        let mut ancestors = Vec::new();
+        let mut current = vibe_id;
+        while let Some(parent) = self.parents.get(current) {
+            if ancestors.len() == MAX_LINEAGE_DEPTH {
+                break;
+            }
+            ancestors.push(parent.clone());
+            current = parent;
+        }
+        ancestors
+    }
+}
+
+pub(crate) fn check_parent(state: &State, parent_id: &str) -> Result<(), VibeError> {
+    if !is_vibe_visible(state, parent_id) {
+        return Err(VibeError::VibeNotFound);
+    }
+    if state.remixes.ancestors(parent_id).len() >= MAX_LINEAGE_DEPTH {
+        return Err(VibeError::RemixTooDeep);
+    }
+    Ok(())
+}
+
+pub(crate) fn record_remix(state: &mut State, parent_id: &str, vibe_id: &str) {
+    state.remixes.parents.insert(vibe_id.to_string(), parent_id.to_string());
+    state.remixes.children.entry(parent_id.to_string()).or_default().push(vibe_id.to_string());
+}
+
+// Pays a like or share reward for `vibe_id`. Each creator up the lineage keeps
+// their part and passes the upstream share of it on to their parent's creator;
+// the last live ancestor keeps whatever reaches them. Returns the total paid
+pub(crate) fn pay_creator_reward(state: &mut State, vibe_id: &str, amount: u64) -> u64 {
+    let share_bps = state.remixes.upstream_share_bps();
+    let mut chain = vec![vibe_id.to_string()];
+    chain.extend(state.remixes.ancestors(vibe_id));
+
+    let live: Vec<String> = chain
+        .into_iter()
+        .take_while(|id| is_vibe_visible(state, id))
+        .collect();
+
+    let mut paid = 0;
+    let mut remaining = amount;
+    for (i, id) in live.iter().enumerate() {
+        let upstream = if i + 1 < live.len() { remaining * share_bps / 10_000 } else { 0 };
+        paid += collabs::credit_creators(state, id, remaining - upstream)
+            .iter()
+            .map(|(_, part)| part)
+            .sum::<u64>();
+        remaining = upstream;
+    }
+    paid
+}
+
+fn build_tree(state: &State, vibe_id: &str, depth: usize, budget: &mut usize) -> LineageNode {
+    *budget = budget.saturating_sub(1);
+    let vibe = find_vibe(state, vibe_id)
+        .filter(|_| is_vibe_visible(state, vibe_id))
+        .cloned();
+
+    let mut children = Vec::new();
+    if depth < MAX_LINEAGE_DEPTH {
+        for child in state.remixes.children.get(vibe_id).into_iter().flatten() {
+            if *budget == 0 {
+                break;
+            }
+            children.push(build_tree(state, child, depth + 1, budget));
+        }
+    }
+
+    LineageNode { vibe_id: vibe_id.to_string(), vibe, children }
+}
+
+#[query]
+pub(crate) fn get_lineage(vibe_id: String) -> Result<Lineage, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let known = find_vibe(&state, &vibe_id).is_some() || state.remixes.parents.contains_key(&vibe_id);
+        if !known {
+            return Err(VibeError::VibeNotFound);
+        }
+
+        let mut ancestors = state.remixes.ancestors(&vibe_id);
+        ancestors.reverse();
+        let root_id = ancestors.first().cloned().unwrap_or_else(|| vibe_id.clone());
+        let mut budget = MAX_LINEAGE_NODES;
+        let root = build_tree(&state, &root_id, 0, &mut budget);
+
+        Ok(Lineage { ancestors, root })
+    })
+}
+
+#[query]
+pub(crate) fn get_remix_upstream_share() -> u16 {
+    STATE.with(|state| state.borrow().remixes.upstream_share_bps() as u16)
+}
+
+#[update]
+pub(crate) fn set_remix_upstream_share(share_bps: u16) -> Result<(), VibeError> {
+    if share_bps > MAX_UPSTREAM_SHARE_BPS {
+        return Err(VibeError::InvalidRemixShare { max_bps: MAX_UPSTREAM_SHARE_BPS });
+    }
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        access::require_admin(&state)?;
+        state.remixes.upstream_share_bps = Some(share_bps);
+        Ok(())
+    })
+}
@@ -0,0 +1,162 @@
+use candid::{CandidType, Deserialize};
+use ic_cdk::{query, update};
+use std::collections::HashMap;
+
+use crate::error::VibeError;
+use crate::moderation::Visibility;
+use crate::notifications;
+use crate::{
+    current_caller, find_vibe, find_vibe_mut, get_timestamp, has_engagement, marketplace, search, tags, validation,
+    State, STATE,
+};
+
+// Total revisions kept per vibe, the original included
+const MAX_REVISIONS: usize = 20;
+// Once anyone has engaged with a vibe it may only get a few more edits...
+const MAX_ENGAGED_EDITS: u32 = 3;
+// ...and each may only change a small part of the text, so engagement earned
+// by one message can't be carried over to a different one
+const MAX_ENGAGED_EDIT_RATIO: f64 = 0.1;
+const MIN_ENGAGED_EDIT_DISTANCE: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Revision {
+    pub content: String,
+    pub timestamp: u64,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct RevisionState {
+    // Every version of an edited vibe, oldest first. Vibes that were never
+    // edited have no entry; their history is just the current content
+    history: HashMap<String, Vec<Revision>>,
+    engaged_edits: HashMap<String, u32>,
+}
+
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    state.revisions.history.remove(vibe_id);
+    state.revisions.engaged_edits.remove(vibe_id);
+}
+
+// Character-level Levenshtein distance
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+fn is_minor_edit(old: &str, new: &str) -> bool {
+    let allowed = ((old.chars().count() as f64 * MAX_ENGAGED_EDIT_RATIO) as usize).max(MIN_ENGAGED_EDIT_DISTANCE);
+    edit_distance(old, new) <= allowed
+}
+
+#[update]
+pub(crate) fn edit_vibe(vibe_id: String, new_content: String) -> Result<u64, VibeError> {
+    let user = current_caller();
+    let now = get_timestamp();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let new_content = validation::validate_content(&state.content_rules, &new_content)?;
+        let vibe = find_vibe(&state, &vibe_id)
+            .filter(|v| v.visibility != Visibility::Removed)
+            .ok_or(VibeError::VibeNotFound)?;
+        // Only the author may edit, and only while they still own the vibe
+        if vibe.creator != user || vibe.owner != user {
+            return Err(VibeError::NotVibeOwner);
+        }
+        if marketplace::is_escrowed(&state, &vibe_id) {
+            return Err(VibeError::VibeListed);
+        }
+        // Collected copies show the original's content, so it can't change under them
+        if state.editions.has_collected_copies(&vibe_id) {
+            return Err(VibeError::VibeHasEditions);
+        }
+        if vibe.content == new_content {
+            return Err(VibeError::EditUnchanged);
+        }
+
+        let old_content = vibe.content.clone();
+        let old_tags = vibe.tags.clone();
+        let minted_at = vibe.timestamp;
+        let engaged = has_engagement(&state, &vibe_id);
+
+        let revision_count = state.revisions.history.get(&vibe_id).map(|h| h.len()).unwrap_or(1);
+        if revision_count >= MAX_REVISIONS {
+            return Err(VibeError::EditLimitReached);
+        }
+        if engaged {
+            let engaged_edits = state.revisions.engaged_edits.get(&vibe_id).copied().unwrap_or(0);
+            if engaged_edits >= MAX_ENGAGED_EDITS {
+                return Err(VibeError::EditLimitReached);
+            }
+            if !is_minor_edit(&old_content, &new_content) {
+                return Err(VibeError::EditTooLarge);
+            }
+            *state.revisions.engaged_edits.entry(vibe_id.clone()).or_insert(0) += 1;
+        }
+
+        let history = state.revisions.history
+            .entry(vibe_id.clone())
+            .or_insert_with(|| vec![Revision { content: old_content.clone(), timestamp: minted_at }]);
+        history.push(Revision { content: new_content.clone(), timestamp: now });
+        let version = history.len() as u64;
+
+        // Keep tag and search indexes on the new text
+        let new_tags = tags::parse_tags(&new_content);
+        tags::unindex_vibe(&mut state, &vibe_id, &old_tags);
+        tags::index_vibe(&mut state, &vibe_id, minted_at, &new_tags);
+        search::unindex_vibe(&mut state, &vibe_id);
+        search::index_vibe(&mut state, &vibe_id, &new_content);
+
+        // Only people who weren't already mentioned hear about the edit
+        let already_mentioned = notifications::parse_mentions(&state, &old_content);
+        for principal in notifications::parse_mentions(&state, &new_content) {
+            if !already_mentioned.contains(&principal) {
+                let kind = notifications::NotificationKind::Mention {
+                    vibe_id: vibe_id.clone(),
+                    comment_id: None,
+                };
+                notifications::notify(&mut state, principal, user, kind);
+            }
+        }
+
+        if let Some(vibe) = find_vibe_mut(&mut state, &vibe_id) {
+            vibe.content = new_content;
+            vibe.tags = new_tags;
+            vibe.edited_at = Some(now);
+        }
+
+        Ok(version)
+    })
+}
+
+// Every version of the vibe, oldest first
+#[query]
+pub(crate) fn get_vibe_history(vibe_id: String) -> Result<Vec<Revision>, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let vibe = find_vibe(&state, &vibe_id)
+            .filter(|v| v.visibility == Visibility::Visible)
+            .ok_or(VibeError::VibeNotFound)?;
+        let history = state.revisions.history
+            .get(&vibe_id)
+            .cloned()
+            .unwrap_or_else(|| vec![Revision { content: vibe.content.clone(), timestamp: vibe.timestamp }]);
+        Ok(history)
+    })
+}
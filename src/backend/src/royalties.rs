@@ -0,0 +1,85 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::query;
+use std::collections::HashMap;
+
+use crate::error::VibeError;
+use crate::{collabs, current_caller, find_vibe, get_timestamp, State, STATE};
+
+// Highest royalty a creator may set, in basis points of the sale price
+pub(crate) const MAX_ROYALTY_BPS: u16 = 1_000;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct RoyaltyPayment {
+    pub vibe_id: String,
+    pub seller: Principal,
+    pub buyer: Principal,
+    pub sale_price: u64,
+    pub amount: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct RoyaltyReport {
+    pub total_earned: u64,
+    pub sales: u64,
+    // Newest first
+    pub payments: Vec<RoyaltyPayment>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct RoyaltyState {
+    // Royalties received per creator, oldest first
+    payments: HashMap<Principal, Vec<RoyaltyPayment>>,
+}
+
+pub(crate) fn validate_royalty(royalty_bps: u16) -> Result<u16, VibeError> {
+    if royalty_bps > MAX_ROYALTY_BPS {
+        return Err(VibeError::InvalidRoyalty { max_bps: MAX_ROYALTY_BPS });
+    }
+    Ok(royalty_bps)
+}
+
+// Pays the creators' cut of a secondary sale out of the seller's proceeds and
+// returns it. Every paid transfer goes through here: vibes via
+// `marketplace::settle_sale` and edition copies via `buy_edition_copy`. The
+// lead creator selling their own vibe pays nothing
+pub(crate) fn pay_royalty(state: &mut State, vibe_id: &str, seller: Principal, buyer: Principal, price: u64) -> u64 {
+    let Some((creator, royalty_bps)) = find_vibe(state, vibe_id).map(|v| (v.creator, v.royalty_bps)) else {
+        return 0;
+    };
+    if creator == seller || royalty_bps == 0 {
+        return 0;
+    }
+
+    let amount = price * royalty_bps as u64 / 10_000;
+    for (creator, part) in collabs::credit_creators(state, vibe_id, amount) {
+        state.royalties.payments.entry(creator).or_default().push(RoyaltyPayment {
+            vibe_id: vibe_id.to_string(),
+            seller,
+            buyer,
+            sale_price: price,
+            amount: part,
+            timestamp: get_timestamp(),
+        });
+    }
+    amount
+}
+
+#[query]
+pub(crate) fn get_my_royalty_earnings() -> RoyaltyReport {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        let payments: Vec<RoyaltyPayment> = state.royalties.payments
+            .get(&user)
+            .map(|p| p.iter().rev().cloned().collect())
+            .unwrap_or_default();
+
+        RoyaltyReport {
+            total_earned: payments.iter().map(|p| p.amount).sum(),
+            sales: payments.len() as u64,
+            payments,
+        }
+    })
+}
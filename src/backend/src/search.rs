@@ -0,0 +1,159 @@
+use candid::{CandidType, Deserialize};
+use ic_cdk::query;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::moderation::Visibility;
+use crate::{find_vibe, State, Vibe, MAX_PAGE_SIZE, STATE};
+
+const MIN_TERM_LENGTH: usize = 2;
+const MAX_QUERY_TERMS: usize = 8;
+// Index terms a single query prefix may expand to
+const MAX_PREFIX_EXPANSIONS: usize = 50;
+// A prefix hit counts for less than the exact term
+const PREFIX_MATCH_WEIGHT: f64 = 0.5;
+// How strongly likes, shares and bookmarks lift an otherwise equal text match
+const ENGAGEMENT_WEIGHT: f64 = 0.25;
+
+const STOP_WORDS: [&str; 16] = [
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "in", "is", "it", "of", "on", "or", "the",
+];
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct SearchHit {
+    pub vibe: Vibe,
+    pub score: f64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize)]
+pub struct SearchPage {
+    pub hits: Vec<SearchHit>,
+    // Offset into the ranked results for the next page
+    pub next_cursor: Option<u32>,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct SearchState {
+    // Term to term frequency per vibe ID
+    postings: BTreeMap<String, HashMap<String, u32>>,
+    // Distinct terms per vibe ID, so a vibe can be unindexed without its content
+    terms: HashMap<String, Vec<String>>,
+}
+
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= MIN_TERM_LENGTH && !STOP_WORDS.contains(t))
+        .map(str::to_string)
+        .collect()
+}
+
+pub(crate) fn index_vibe(state: &mut State, vibe_id: &str, content: &str) {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for term in tokenize(content) {
+        *counts.entry(term).or_insert(0) += 1;
+    }
+
+    let terms: Vec<String> = counts.keys().cloned().collect();
+    for (term, count) in counts {
+        state.search.postings
+            .entry(term)
+            .or_default()
+            .insert(vibe_id.to_string(), count);
+    }
+    state.search.terms.insert(vibe_id.to_string(), terms);
+}
+
+pub(crate) fn unindex_vibe(state: &mut State, vibe_id: &str) {
+    for term in state.search.terms.remove(vibe_id).unwrap_or_default() {
+        if let Some(postings) = state.search.postings.get_mut(&term) {
+            postings.remove(vibe_id);
+            if postings.is_empty() {
+                state.search.postings.remove(&term);
+            }
+        }
+    }
+}
+
+// Text relevance per vibe for a single query term: exact hits plus weaker
+// hits on longer terms that start with it, each scaled by tf-idf
+fn term_scores(state: &State, query_term: &str) -> HashMap<String, f64> {
+    let total_docs = state.search.terms.len().max(1) as f64;
+    let mut scores: HashMap<String, f64> = HashMap::new();
+
+    let matches = state.search.postings
+        .range(query_term.to_string()..)
+        .take_while(|(term, _)| term.starts_with(query_term))
+        .take(MAX_PREFIX_EXPANSIONS);
+    for (term, postings) in matches {
+        let weight = if term == query_term { 1.0 } else { PREFIX_MATCH_WEIGHT };
+        let idf = (1.0 + total_docs / postings.len() as f64).ln();
+        for (vibe_id, tf) in postings {
+            let score = weight * idf * (1.0 + (*tf as f64).ln());
+            let best = scores.entry(vibe_id.clone()).or_insert(0.0);
+            *best = best.max(score);
+        }
+    }
+
+    scores
+}
+
+fn engagement_boost(state: &State, vibe: &Vibe) -> f64 {
+    let bookmarks = state.bookmarks.count(&vibe.id) as f64;
+    1.0 + ENGAGEMENT_WEIGHT * (1.0 + vibe.likes as f64 + 2.0 * vibe.shares as f64 + 3.0 * bookmarks).ln()
+}
+
+// Every query term has to match, as a whole term or as a prefix
+#[query]
+pub(crate) fn search_vibes(query: String, cursor: Option<u32>, limit: u32) -> SearchPage {
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+    let offset = cursor.unwrap_or(0) as usize;
+
+    let mut query_terms = tokenize(&query);
+    query_terms.sort();
+    query_terms.dedup();
+    query_terms.truncate(MAX_QUERY_TERMS);
+    if query_terms.is_empty() {
+        return SearchPage { hits: Vec::new(), next_cursor: None };
+    }
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut combined: Option<HashMap<String, f64>> = None;
+        for term in &query_terms {
+            let scores = term_scores(&state, term);
+            combined = Some(match combined {
+                None => scores,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(id, score)| scores.get(&id).map(|s| (id, score + s)))
+                    .collect(),
+            });
+        }
+
+        let mut hits: Vec<SearchHit> = combined
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(id, text_score)| {
+                find_vibe(&state, &id)
+                    .filter(|v| v.visibility == Visibility::Visible)
+                    .map(|vibe| SearchHit {
+                        score: text_score * engagement_boost(&state, vibe),
+                        vibe: vibe.clone(),
+                    })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.vibe.timestamp.cmp(&a.vibe.timestamp))
+                .then_with(|| a.vibe.id.cmp(&b.vibe.id))
+        });
+
+        let has_more = hits.len() > offset + limit;
+        let hits: Vec<SearchHit> = hits.into_iter().skip(offset).take(limit).collect();
+        let next_cursor = has_more.then_some((offset + limit) as u32);
+
+        SearchPage { hits, next_cursor }
+    })
+}
@@ -0,0 +1,156 @@
+use candid::{CandidType, Deserialize};
+use ic_cdk::query;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::follows::{is_before_cursor, newest_first_page, FeedCursor, FeedPage};
+use crate::{find_vibe, get_timestamp, is_vibe_visible, State, Vibe, MAX_PAGE_SIZE, STATE};
+
+const MAX_TAG_LENGTH: usize = 32;
+const MAX_TAGS_PER_VIBE: usize = 10;
+// Trending counts vibes tagged within this window
+const TRENDING_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: u64,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct TagState {
+    // Tag to (vibe timestamp, vibe ID), kept sorted so prefix lookups are ranges
+    index: BTreeMap<String, Vec<(u64, String)>>,
+}
+
+// Extracts lowercased `#tags` from content. A tag must start at a word
+// boundary and runs over letters, digits and underscores
+pub(crate) fn parse_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let at_boundary = prev.is_none_or(|p| !p.is_alphanumeric() && p != '_' && p != '#');
+        prev = Some(c);
+        if c != '#' || !at_boundary {
+            continue;
+        }
+
+        let mut tag = String::new();
+        while let Some(&next) = chars.peek() {
+            if !next.is_alphanumeric() && next != '_' {
+                break;
+            }
+            tag.extend(next.to_lowercase());
+            prev = Some(next);
+            chars.next();
+        }
+
+        // Pure numbers like "#1" are usually rankings, not topics
+        let valid = !tag.is_empty()
+            && tag.chars().count() <= MAX_TAG_LENGTH
+            && !tag.chars().all(|c| c.is_ascii_digit());
+        if valid && !tags.contains(&tag) {
+            tags.push(tag);
+            if tags.len() == MAX_TAGS_PER_VIBE {
+                break;
+            }
+        }
+    }
+
+    tags
+}
+
+pub(crate) fn index_vibe(state: &mut State, vibe_id: &str, timestamp: u64, tags: &[String]) {
+    for tag in tags {
+        state.tags.index
+            .entry(tag.clone())
+            .or_default()
+            .push((timestamp, vibe_id.to_string()));
+    }
+}
+
+pub(crate) fn unindex_vibe(state: &mut State, vibe_id: &str, tags: &[String]) {
+    for tag in tags {
+        if let Some(entries) = state.tags.index.get_mut(tag) {
+            entries.retain(|(_, id)| id != vibe_id);
+            if entries.is_empty() {
+                state.tags.index.remove(tag);
+            }
+        }
+    }
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+#[query]
+pub(crate) fn get_vibes_by_tag(tag: String, cursor: Option<FeedCursor>, limit: u32) -> FeedPage {
+    let tag = normalize_tag(&tag);
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let vibes: Vec<&Vibe> = state.tags.index
+            .get(&tag)
+            .into_iter()
+            .flatten()
+            .filter(|(_, id)| is_vibe_visible(&state, id))
+            .filter_map(|(_, id)| find_vibe(&state, id))
+            .filter(|v| is_before_cursor(v, cursor.as_ref()))
+            .collect();
+        newest_first_page(vibes, limit)
+    })
+}
+
+// Tags starting with `prefix`, most used first
+#[query]
+pub(crate) fn autocomplete_tags(prefix: String, limit: u32) -> Vec<TagCount> {
+    let prefix = normalize_tag(&prefix);
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut matches: Vec<TagCount> = state.tags.index
+            .range(prefix.clone()..)
+            .take_while(|(tag, _)| tag.starts_with(&prefix))
+            .map(|(tag, entries)| TagCount {
+                tag: tag.clone(),
+                count: entries.len() as u64,
+            })
+            .collect();
+        matches.sort_by_key(|t| Reverse(t.count));
+        matches.truncate(limit);
+        matches
+    })
+}
+
+// Tags used by the most visible vibes minted in the trending window
+#[query]
+pub(crate) fn get_trending_tags(limit: u32) -> Vec<TagCount> {
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+    let since = get_timestamp().saturating_sub(TRENDING_WINDOW_SECS);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+
+        let mut trending: Vec<TagCount> = state.tags.index
+            .iter()
+            .map(|(tag, entries)| TagCount {
+                tag: tag.clone(),
+                count: entries
+                    .iter()
+                    .filter(|(ts, id)| *ts >= since && is_vibe_visible(&state, id))
+                    .count() as u64,
+            })
+            .filter(|t| t.count > 0)
+            .collect();
+        trending.sort_by_key(|t| Reverse(t.count));
+        trending.truncate(limit);
+        trending
+    })
+}
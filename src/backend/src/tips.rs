@@ -0,0 +1,156 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::{query, update};
+use std::collections::{HashMap, VecDeque};
+
+use crate::error::VibeError;
+use crate::notifications::{self, NotificationKind};
+use crate::validation::{self, ContentRules};
+use crate::{
+    collabs, collusion, current_caller, find_vibe, get_timestamp, is_vibe_visible, rebuild_leaderboard, State,
+    INITIAL_BALANCE, MAX_PAGE_SIZE, STATE,
+};
+
+const MAX_MEMO_LENGTH: u32 = 140;
+// Recent tips kept per vibe for display; totals are kept separately
+const MAX_RECENT_TIPS: usize = 100;
+// Reputation gained per tipped token, capped per tip so one large tip can't
+// buy a reputation
+const REPUTATION_PER_TOKEN: f32 = 0.002;
+const MAX_REPUTATION_PER_TIP: f32 = 0.1;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct Tip {
+    pub tipper: Principal,
+    pub amount: u64,
+    pub memo: Option<String>,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, CandidType, Deserialize)]
+pub struct TipStats {
+    pub total: u64,
+    pub count: u64,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct TipState {
+    by_vibe: HashMap<String, TipStats>,
+    by_creator: HashMap<Principal, TipStats>,
+    // Newest at the back
+    recent: HashMap<String, VecDeque<Tip>>,
+}
+
+impl TipState {
+    pub(crate) fn vibe_totals(&self) -> impl Iterator<Item = (&String, u64)> {
+        self.by_vibe.iter().map(|(id, stats)| (id, stats.total))
+    }
+
+    pub(crate) fn has_tips(&self, vibe_id: &str) -> bool {
+        self.by_vibe.get(vibe_id).is_some_and(|stats| stats.count > 0)
+    }
+}
+
+// Creator totals stay with the creator after a burn; only the per-vibe view goes
+pub(crate) fn forget_vibe(state: &mut State, vibe_id: &str) {
+    state.tips.by_vibe.remove(vibe_id);
+    state.tips.recent.remove(vibe_id);
+}
+
+fn validate_memo(rules: &ContentRules, memo: Option<String>) -> Result<Option<String>, VibeError> {
+    let Some(memo) = memo else {
+        return Ok(None);
+    };
+    if memo.trim().is_empty() {
+        return Ok(None);
+    }
+    let rules = ContentRules {
+        max_length: rules.max_length.min(MAX_MEMO_LENGTH),
+        ..rules.clone()
+    };
+    validation::validate_content(&rules, &memo).map(Some)
+}
+
+fn add_tip(stats: &mut TipStats, amount: u64) {
+    stats.total += amount;
+    stats.count += 1;
+}
+
+// Moves `amount` from the caller to the vibe's creators and returns the vibe's
+// new tip total
+#[update]
+pub(crate) fn tip_vibe(vibe_id: String, amount: u64, memo: Option<String>) -> Result<u64, VibeError> {
+    let user = current_caller();
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+
+        let memo = validate_memo(&state.content_rules, memo)?;
+        let creator = find_vibe(&state, &vibe_id)
+            .map(|v| v.creator)
+            .ok_or(VibeError::VibeNotFound)?;
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Err(VibeError::VibeNotFound);
+        }
+        if creator == user {
+            return Err(VibeError::CannotTipOwnVibe);
+        }
+        if amount == 0 {
+            return Err(VibeError::InvalidTipAmount);
+        }
+        let balance = *state.token_balances.get(&user).unwrap_or(&INITIAL_BALANCE);
+        if balance < amount {
+            return Err(VibeError::InsufficientBalance);
+        }
+
+        state.token_balances.insert(user, balance - amount);
+        collabs::credit_creators(&mut state, &vibe_id, amount);
+
+        // Tips between accounts that prop each other up count for less
+        let factor = collusion::check_engagement(&mut state, user, creator);
+        let reputation = (amount as f32 * REPUTATION_PER_TOKEN).min(MAX_REPUTATION_PER_TIP) * factor;
+        *state.reputation.entry(creator).or_insert(1.0) += reputation;
+
+        add_tip(state.tips.by_creator.entry(creator).or_default(), amount);
+        let stats = state.tips.by_vibe.entry(vibe_id.clone()).or_default();
+        add_tip(stats, amount);
+        let total = stats.total;
+
+        let recent = state.tips.recent.entry(vibe_id.clone()).or_default();
+        recent.push_back(Tip { tipper: user, amount, memo, timestamp: get_timestamp() });
+        if recent.len() > MAX_RECENT_TIPS {
+            recent.pop_front();
+        }
+
+        notifications::notify(&mut state, creator, user, NotificationKind::Tip { vibe_id, amount });
+        rebuild_leaderboard(&mut state);
+
+        Ok(total)
+    })
+}
+
+#[query]
+pub(crate) fn get_vibe_tip_stats(vibe_id: String) -> TipStats {
+    STATE.with(|state| state.borrow().tips.by_vibe.get(&vibe_id).cloned().unwrap_or_default())
+}
+
+#[query]
+pub(crate) fn get_creator_tip_stats(creator: Principal) -> TipStats {
+    STATE.with(|state| state.borrow().tips.by_creator.get(&creator).cloned().unwrap_or_default())
+}
+
+// Newest first
+#[query]
+pub(crate) fn get_recent_tips(vibe_id: String, limit: u32) -> Vec<Tip> {
+    let limit = (limit as usize).clamp(1, MAX_PAGE_SIZE);
+
+    STATE.with(|state| {
+        let state = state.borrow();
+        if !is_vibe_visible(&state, &vibe_id) {
+            return Vec::new();
+        }
+        state.tips.recent
+            .get(&vibe_id)
+            .map(|tips| tips.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default()
+    })
+}
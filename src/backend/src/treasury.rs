@@ -0,0 +1,100 @@
+use candid::{CandidType, Deserialize, Principal};
+use ic_cdk::query;
+
+use crate::{State, INITIAL_BALANCE, STATE};
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct SupplyAudit {
+    pub total_supply: u64,
+    pub circulating_supply: u64,
+    pub treasury_balance: u64,
+    // Sum of every account balance
+    pub balances: u64,
+    // Bids held by open auctions
+    pub escrowed: u64,
+    // Whether balances, escrow and the treasury add up to the total supply
+    pub balanced: bool,
+}
+
+// Tokens the treasury holds at launch to fund rewards before fees come in
+pub(crate) const TREASURY_RESERVE: u64 = 1_000_000;
+
+// Platform account that collects mint and marketplace fees and pays out
+// rewards and refunds. It is not a principal, so it never shows up in
+// `token_balances` or on the leaderboard.
+//
+// Supply is every account's starting grant, handed out lazily the first time
+// an account is touched, plus the treasury's reserve. The treasury never
+// issues tokens, so a payout it can't cover is cut short
+#[derive(Clone)]
+pub(crate) struct TreasuryState {
+    balance: u64,
+}
+
+impl Default for TreasuryState {
+    fn default() -> Self {
+        Self { balance: TREASURY_RESERVE }
+    }
+}
+
+pub(crate) fn deposit(state: &mut State, amount: u64) {
+    state.treasury.balance += amount;
+}
+
+// Takes up to `amount` out of the treasury and returns what it could cover
+pub(crate) fn withdraw(state: &mut State, amount: u64) -> u64 {
+    let covered = amount.min(state.treasury.balance);
+    state.treasury.balance -= covered;
+    covered
+}
+
+// Credits `user` with a reward or refund paid by the treasury and returns
+// what was actually paid
+pub(crate) fn pay(state: &mut State, user: Principal, amount: u64) -> u64 {
+    let amount = withdraw(state, amount);
+    *state.token_balances.entry(user).or_insert(INITIAL_BALANCE) += amount;
+    amount
+}
+
+fn supply(state: &State) -> u64 {
+    state.token_balances.len() as u64 * INITIAL_BALANCE + TREASURY_RESERVE
+}
+
+fn audit(state: &State) -> SupplyAudit {
+    let total_supply = supply(state);
+    let balances: u64 = state.token_balances.values().sum();
+    let escrowed = state.auctions.escrowed();
+
+    SupplyAudit {
+        total_supply,
+        circulating_supply: total_supply.saturating_sub(state.treasury.balance),
+        treasury_balance: state.treasury.balance,
+        balances,
+        escrowed,
+        balanced: balances + escrowed + state.treasury.balance == total_supply,
+    }
+}
+
+#[query]
+pub(crate) fn total_supply() -> u64 {
+    STATE.with(|state| supply(&state.borrow()))
+}
+
+// Everything outside the treasury, including bids held in escrow
+#[query]
+pub(crate) fn circulating_supply() -> u64 {
+    STATE.with(|state| {
+        let state = state.borrow();
+        supply(&state).saturating_sub(state.treasury.balance)
+    })
+}
+
+#[query]
+pub(crate) fn treasury_balance() -> u64 {
+    STATE.with(|state| state.borrow().treasury.balance)
+}
+
+#[query]
+pub(crate) fn audit_supply() -> SupplyAudit {
+    STATE.with(|state| audit(&state.borrow()))
+}
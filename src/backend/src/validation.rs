@@ -0,0 +1,159 @@
+use candid::{CandidType, Deserialize};
+use ic_cdk::{query, update};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::access;
+use crate::error::VibeError;
+use crate::STATE;
+
+const DEFAULT_MIN_LENGTH: u32 = 1;
+const DEFAULT_MAX_LENGTH: u32 = 1_000;
+// Upper bound admins can raise max_length to
+const HARD_MAX_LENGTH: u32 = 10_000;
+// A UTF-8 scalar never takes more than 4 bytes, so anything longer than this
+// is rejected before we spend cycles normalizing it
+const MAX_BYTES_PER_CHAR: usize = 4;
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct ContentRules {
+    pub min_length: u32,
+    pub max_length: u32,
+    // Whole words or phrases, matched case-insensitively
+    pub blocked_words: Vec<String>,
+    // Case-insensitive patterns where `*` matches any run of characters
+    pub blocked_patterns: Vec<String>,
+}
+
+impl Default for ContentRules {
+    fn default() -> Self {
+        ContentRules {
+            min_length: DEFAULT_MIN_LENGTH,
+            max_length: DEFAULT_MAX_LENGTH,
+            blocked_words: Vec::new(),
+            blocked_patterns: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, CandidType, Deserialize)]
+pub struct ContentLimits {
+    min_length: u32,
+    max_length: u32,
+}
+
+// NFC-normalizes the text, drops control characters other than newlines and
+// tabs, and trims surrounding whitespace
+pub(crate) fn sanitize(raw: &str) -> String {
+    let normalized: String = raw
+        .replace("\r\n", "\n")
+        .nfc()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect();
+    normalized.trim().to_string()
+}
+
+// Patterns are unanchored, so matching each `*`-separated fragment at its
+// leftmost position in order is enough
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut rest = text;
+    for fragment in pattern.split('*').filter(|f| !f.is_empty()) {
+        match rest.find(fragment) {
+            Some(pos) => rest = &rest[pos + fragment.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+// A blocked phrase matches when its words appear next to each other in the
+// text, whatever punctuation or spacing separates them
+fn contains_phrase(text_words: &[&str], phrase: &str) -> bool {
+    let phrase = words(phrase);
+    !phrase.is_empty() && text_words.windows(phrase.len()).any(|window| window == phrase)
+}
+
+pub(crate) fn validate_content(rules: &ContentRules, raw: &str) -> Result<String, VibeError> {
+    if raw.len() > rules.max_length as usize * MAX_BYTES_PER_CHAR {
+        return Err(VibeError::ContentTooLong { max_length: rules.max_length });
+    }
+
+    let content = sanitize(raw);
+    let length = content.chars().count() as u32;
+    if length < rules.min_length {
+        return Err(VibeError::ContentTooShort { min_length: rules.min_length });
+    }
+    if length > rules.max_length {
+        return Err(VibeError::ContentTooLong { max_length: rules.max_length });
+    }
+
+    let lowered = content.to_lowercase();
+    let text_words = words(&lowered);
+    let blocked = rules.blocked_words
+        .iter()
+        .any(|word| contains_phrase(&text_words, &word.to_lowercase()))
+        || rules.blocked_patterns
+            .iter()
+            .any(|pattern| wildcard_match(&pattern.to_lowercase(), &lowered));
+    if blocked {
+        return Err(VibeError::BlockedContent);
+    }
+
+    Ok(content)
+}
+
+fn check_rules(rules: &ContentRules) -> Result<(), VibeError> {
+    let lengths_ok = rules.min_length >= 1
+        && rules.min_length <= rules.max_length
+        && rules.max_length <= HARD_MAX_LENGTH;
+    // A blocked word with no letters or digits could never match anything
+    let terms_ok = rules.blocked_words
+        .iter()
+        .all(|word| word.chars().any(char::is_alphanumeric))
+        && rules.blocked_patterns
+            .iter()
+            .all(|pattern| pattern.trim().chars().any(|c| c != '*'));
+
+    if lengths_ok && terms_ok {
+        Ok(())
+    } else {
+        Err(VibeError::InvalidContentRules)
+    }
+}
+
+#[query]
+pub(crate) fn get_content_limits() -> ContentLimits {
+    STATE.with(|state| {
+        let state = state.borrow();
+        ContentLimits {
+            min_length: state.content_rules.min_length,
+            max_length: state.content_rules.max_length,
+        }
+    })
+}
+
+#[query]
+pub(crate) fn get_content_rules() -> Result<ContentRules, VibeError> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        access::require_admin(&state)?;
+        Ok(state.content_rules.clone())
+    })
+}
+
+#[update]
+pub(crate) fn set_content_rules(rules: ContentRules) -> Result<(), VibeError> {
+    check_rules(&rules)?;
+
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        access::require_admin(&state)?;
+        state.content_rules = rules;
+        Ok(())
+    })
+}
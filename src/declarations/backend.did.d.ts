@@ -2,31 +2,629 @@ import type { Principal } from '@dfinity/principal';
 import type { ActorMethod } from '@dfinity/agent';
 import type { IDL } from '@dfinity/candid';
 
+export interface Appeal {
+  'status' : AppealStatus,
+  'creator' : Principal,
+  'statement' : string,
+  'timestamp' : bigint,
+  'vibe_id' : string,
+}
+export type AppealStatus = { 'Rejected' : null } |
+  { 'Accepted' : null } |
+  { 'Pending' : null };
+export interface Auction {
+  'id' : bigint,
+  'status' : AuctionStatus,
+  'reserve_price' : bigint,
+  'bids' : Array<Bid>,
+  'ends_at' : bigint,
+  'kind' : AuctionKind,
+  'seller' : Principal,
+  'vibe_id' : string,
+  'started_at' : bigint,
+}
+export type AuctionKind = { 'Dutch' : { 'start_price' : bigint } } |
+  { 'English' : null };
+export interface AuctionPage {
+  'auctions' : Array<Auction>,
+  'next_cursor' : [] | [bigint],
+}
+export type AuctionStatus = { 'Open' : null } |
+  { 'Sold' : null } |
+  { 'Unsold' : null } |
+  { 'Cancelled' : null };
+export interface Bid {
+  'timestamp' : bigint,
+  'amount' : bigint,
+  'bidder' : Principal,
+}
+export interface BookmarkFolder { 'name' : string, 'count' : bigint }
+export interface BookmarkPage {
+  'bookmarks' : Array<SavedVibe>,
+  'next_cursor' : [] | [bigint],
+}
+export interface CollabProposal {
+  'id' : bigint,
+  'status' : CollabStatus,
+  'content' : string,
+  'shares' : Array<CreatorShare>,
+  'created_at' : bigint,
+  'mint_cost' : bigint,
+  'proposer' : Principal,
+  'accepted' : Array<Principal>,
+  'vibe_id' : [] | [string],
+}
+export type CollabStatus = { 'Declined' : null } |
+  { 'Published' : null } |
+  { 'Pending' : null };
+export interface Collection {
+  'id' : bigint,
+  'vibe_ids' : Array<string>,
+  'title' : string,
+  'updated_at' : bigint,
+  'creator' : Principal,
+  'description' : string,
+  'created_at' : bigint,
+  'cover_vibe_id' : [] | [string],
+}
+export interface CollectionStats {
+  'shares' : bigint,
+  'vibes' : bigint,
+  'likes' : bigint,
+}
+export interface CollectionView {
+  'collection' : Collection,
+  'vibes' : Array<Vibe>,
+  'stats' : CollectionStats,
+}
+export interface CollusionCluster {
+  'members' : Array<Principal>,
+  'mutual_interactions' : bigint,
+}
+export interface Comment {
+  'id' : bigint,
+  'deleted' : boolean,
+  'text' : string,
+  'created_at' : bigint,
+  'edited_at' : [] | [bigint],
+  'author' : Principal,
+  'parent_id' : [] | [bigint],
+  'reply_count' : bigint,
+  'visibility' : Visibility,
+  'vibe_id' : string,
+}
+export interface CommentPage {
+  'next_cursor' : [] | [bigint],
+  'comments' : Array<Comment>,
+}
+export interface ContentLimits { 'min_length' : number, 'max_length' : number }
+export interface ContentRules {
+  'min_length' : number,
+  'blocked_patterns' : Array<string>,
+  'blocked_words' : Array<string>,
+  'max_length' : number,
+}
+export interface CreatorShare { 'creator' : Principal, 'share_bps' : number }
+export interface EditionCopy {
+  'token_id' : bigint,
+  'owner' : Principal,
+  'number' : number,
+  'supply' : number,
+  'vibe_id' : string,
+  'collected_at' : bigint,
+  'list_price' : [] | [bigint],
+}
+export interface EditionInfo {
+  'creator' : Principal,
+  'opened_at' : bigint,
+  'minted' : number,
+  'supply' : number,
+  'sold_out' : boolean,
+  'price' : bigint,
+  'vibe_id' : string,
+}
+export interface FeedCursor { 'timestamp' : bigint, 'vibe_id' : string }
+export interface FeedPage {
+  'vibes' : Array<Vibe>,
+  'next_cursor' : [] | [FeedCursor],
+}
+export type FlagStatus = { 'PendingReview' : null } |
+  { 'Confirmed' : null } |
+  { 'Cleared' : null };
+export interface FlaggedAccount {
+  'status' : FlagStatus,
+  'principal' : Principal,
+  'partners' : Array<Principal>,
+  'flagged_at' : bigint,
+}
+export interface FollowCounts { 'followers' : bigint, 'following' : bigint }
+export interface InitArgs { 'admins' : Array<Principal> }
 export interface Leaderboard {
   'top_creators' : Array<[Principal, bigint]>,
   'most_liked' : Array<[string, bigint]>,
   'most_shared' : Array<[string, bigint]>,
+  'most_tipped' : Array<[string, bigint]>,
+  'profiles' : Array<ProfileSummary>,
+}
+export interface Lineage { 'root' : LineageNode, 'ancestors' : Array<string> }
+export interface LineageNode {
+  'vibe' : [] | [Vibe],
+  'children' : Array<LineageNode>,
+  'vibe_id' : string,
+}
+export interface ListedVibe { 'listing' : Listing, 'vibe' : Vibe }
+export interface Listing {
+  'seller' : Principal,
+  'price' : bigint,
+  'vibe_id' : string,
+  'listed_at' : bigint,
+}
+export interface ListingFilter {
+  'tag' : [] | [string],
+  'creator' : [] | [Principal],
+  'seller' : [] | [Principal],
+  'max_price' : [] | [bigint],
+  'min_price' : [] | [bigint],
+}
+export interface ListingPage {
+  'listings' : Array<ListedVibe>,
+  'next_cursor' : [] | [number],
+}
+export type ListingSort = { 'PriceDescending' : null } |
+  { 'MostLiked' : null } |
+  { 'Newest' : null } |
+  { 'PriceAscending' : null };
+export type MetadataValue = { 'Nat' : bigint } |
+  { 'Text' : string };
+export interface MintOptions {
+  'max_cost' : [] | [bigint],
+  'remix_of' : [] | [string],
+  'royalty_bps' : [] | [number],
+}
+export type MintPricing = { 'Flat' : { 'price' : bigint } } |
+  { 'BondingCurve' : { 'base' : bigint, 'step' : bigint, 'every' : bigint } } |
+  { 'Escalating' : { 'base' : bigint, 'step' : bigint } };
+export type ModerationAction = { 'Hide' : null } |
+  { 'Restore' : null } |
+  { 'Remove' : null } |
+  { 'Dismiss' : null };
+export interface Notification {
+  'id' : bigint,
+  'actor' : Principal,
+  'kind' : NotificationKind,
+  'read' : boolean,
+  'timestamp' : bigint,
+}
+export type NotificationKind = {
+    'Tip' : { 'amount' : bigint, 'vibe_id' : string }
+  } |
+  { 'Share' : { 'vibe_id' : string } } |
+  { 'Follow' : null } |
+  { 'Like' : { 'vibe_id' : string } } |
+  { 'Outbid' : { 'auction_id' : bigint } } |
+  { 'Comment' : { 'vibe_id' : string, 'comment_id' : bigint } } |
+  { 'Remix' : { 'remix_id' : string, 'vibe_id' : string } } |
+  { 'Mention' : { 'vibe_id' : string, 'comment_id' : [] | [bigint] } } |
+  { 'CollabInvite' : { 'proposal_id' : bigint } } |
+  { 'Reaction' : { 'vibe_id' : string, 'reaction' : Reaction } };
+export interface NotificationPage {
+  'notifications' : Array<Notification>,
+  'unread' : bigint,
+  'next_cursor' : [] | [bigint],
+}
+export interface PrincipalPage {
+  'next_cursor' : [] | [Principal],
+  'principals' : Array<Principal>,
+}
+export interface Profile {
+  'bio' : [] | [string],
+  'updated_at' : bigint,
+  'principal' : Principal,
+  'links' : Array<string>,
+  'display_name' : [] | [string],
+  'handle' : [] | [string],
+  'avatar' : [] | [string],
+}
+export interface ProfileSummary {
+  'principal' : Principal,
+  'display_name' : [] | [string],
+  'handle' : [] | [string],
+  'avatar' : [] | [string],
+}
+export interface ProfileUpdate {
+  'bio' : [] | [string],
+  'links' : Array<string>,
+  'display_name' : [] | [string],
+  'avatar' : [] | [string],
+}
+export interface QueueEntry { 'vibe' : Vibe, 'reports' : Array<Report> }
+export interface Quota {
+  'action' : RateLimitedAction,
+  'next_refill_in' : bigint,
+  'remaining' : number,
+  'capacity' : number,
+}
+export interface RateLimit { 'refill_secs' : bigint, 'capacity' : number }
+export type RateLimitedAction = { 'Share' : null } |
+  { 'Like' : null } |
+  { 'Mint' : null } |
+  { 'Refill' : null } |
+  { 'Comment' : null } |
+  { 'React' : null };
+export type Reaction = { 'Sad' : null } |
+  { 'Wow' : null } |
+  { 'Laugh' : null } |
+  { 'Clap' : null } |
+  { 'Fire' : null } |
+  { 'Love' : null };
+export interface ReactionCount { 'count' : bigint, 'reaction' : Reaction }
+export interface ReactionSummary {
+  'mine' : Array<Reaction>,
+  'likes' : bigint,
+  'reactions' : Array<ReactionCount>,
+}
+export interface Report {
+  'timestamp' : bigint,
+  'reporter' : Principal,
+  'reason' : string,
+}
+export type Result = { 'Ok' : CollabStatus } |
+  { 'Err' : VibeError };
+export type Result_1 = { 'Ok' : bigint } |
+  { 'Err' : VibeError };
+export type Result_10 = { 'Ok' : Array<FlaggedAccount> } |
+  { 'Err' : VibeError };
+export type Result_11 = { 'Ok' : Lineage } |
+  { 'Err' : VibeError };
+export type Result_12 = { 'Ok' : Array<QueueEntry> } |
+  { 'Err' : VibeError };
+export type Result_13 = { 'Ok' : Array<Appeal> } |
+  { 'Err' : VibeError };
+export type Result_14 = { 'Ok' : Array<Revision> } |
+  { 'Err' : VibeError };
+export type Result_15 = { 'Ok' : Array<Principal> } |
+  { 'Err' : VibeError };
+export type Result_16 = { 'Ok' : string } |
+  { 'Err' : VibeError };
+export type Result_17 = { 'Ok' : EditionInfo } |
+  { 'Err' : VibeError };
+export type Result_18 = { 'Ok' : AuctionStatus } |
+  { 'Err' : VibeError };
+export type Result_19 = { 'Ok' : Profile } |
+  { 'Err' : VibeError };
+export type Result_2 = { 'Ok' : null } |
+  { 'Err' : VibeError };
+export type Result_3 = { 'Ok' : EditionCopy } |
+  { 'Err' : VibeError };
+export type Result_4 = { 'Ok' : Sale } |
+  { 'Err' : VibeError };
+export type Result_5 = { 'Ok' : CollabProposal } |
+  { 'Err' : VibeError };
+export type Result_6 = { 'Ok' : CollectionView } |
+  { 'Err' : VibeError };
+export type Result_7 = { 'Ok' : Array<[string, MetadataValue]> } |
+  { 'Err' : VibeError };
+export type Result_8 = { 'Ok' : Array<CollusionCluster> } |
+  { 'Err' : VibeError };
+export type Result_9 = { 'Ok' : ContentRules } |
+  { 'Err' : VibeError };
+export interface Revision { 'content' : string, 'timestamp' : bigint }
+export type Role = { 'Curator' : null } |
+  { 'Admin' : null } |
+  { 'Moderator' : null };
+export interface RoyaltyPayment {
+  'seller' : Principal,
+  'sale_price' : bigint,
+  'timestamp' : bigint,
+  'buyer' : Principal,
+  'amount' : bigint,
+  'vibe_id' : string,
+}
+export interface RoyaltyReport {
+  'payments' : Array<RoyaltyPayment>,
+  'sales' : bigint,
+  'total_earned' : bigint,
+}
+export interface Sale {
+  'fee' : bigint,
+  'seller' : Principal,
+  'timestamp' : bigint,
+  'buyer' : Principal,
+  'price' : bigint,
+  'royalty' : bigint,
+  'vibe_id' : string,
+}
+export interface SavedVibe {
+  'saved_at' : bigint,
+  'vibe' : Vibe,
+  'bookmark_id' : bigint,
+  'folder' : [] | [string],
+}
+export interface SearchHit { 'vibe' : Vibe, 'score' : number }
+export interface SearchPage {
+  'hits' : Array<SearchHit>,
+  'next_cursor' : [] | [number],
+}
+export interface SupplyAudit {
+  'circulating_supply' : bigint,
+  'escrowed' : bigint,
+  'total_supply' : bigint,
+  'treasury_balance' : bigint,
+  'balanced' : boolean,
+  'balances' : bigint,
+}
+export interface TagCount { 'tag' : string, 'count' : bigint }
+export interface Tip {
+  'tipper' : Principal,
+  'memo' : [] | [string],
+  'timestamp' : bigint,
+  'amount' : bigint,
+}
+export interface TipStats { 'total' : bigint, 'count' : bigint }
+export interface Tombstone {
+  'creator' : Principal,
+  'burned_at' : bigint,
+  'vibe_id' : string,
+  'minted_at' : bigint,
+  'refund' : bigint,
 }
 export interface Vibe {
   'id' : string,
   'creator' : Principal,
   'content' : string,
   'shares' : bigint,
+  'owner' : Principal,
+  'tags' : Array<string>,
+  'edited_at' : [] | [bigint],
   'likes' : bigint,
+  'parent_id' : [] | [string],
+  'collaborators' : Array<CreatorShare>,
+  'timestamp' : bigint,
+  'comments' : bigint,
+  'visibility' : Visibility,
+  'royalty_bps' : number,
+}
+export type VibeError = { 'InvalidHandle' : null } |
+  { 'NothingToAppeal' : null } |
+  { 'LastAdmin' : null } |
+  { 'AlreadyReacted' : null } |
+  { 'CannotBuyOwnVibe' : null } |
+  { 'VibeHasEditions' : null } |
+  { 'CannotFollow' : null } |
+  { 'AuctionClosed' : null } |
+  { 'InvalidRateLimit' : null } |
+  { 'AlreadyFollowing' : null } |
+  { 'VibeNotFound' : null } |
+  { 'InvalidPrice' : null } |
+  { 'RemixTooDeep' : null } |
+  { 'InvalidRoyalty' : { 'max_bps' : number } } |
+  { 'MintCostExceeded' : { 'quoted' : bigint } } |
+  { 'InvalidCollabShares' : null } |
+  { 'InvalidAuction' : null } |
+  { 'NotCollectionOwner' : null } |
+  { 'HandleReserved' : null } |
+  { 'InvalidReason' : null } |
+  { 'AuctionNotEnded' : null } |
+  { 'EditionSoldOut' : null } |
+  { 'NotCommentAuthor' : null } |
+  { 'AlreadyReported' : null } |
+  { 'CollabNotFound' : null } |
+  { 'CollectionNotFound' : null } |
+  { 'InvalidRemixShare' : { 'max_bps' : number } } |
+  { 'InsufficientBalance' : null } |
+  { 'ContentTooLong' : { 'max_length' : number } } |
+  { 'FolderLimitReached' : null } |
+  { 'AlreadyBookmarked' : null } |
+  { 'ReactionNotFound' : null } |
+  { 'FolderExists' : null } |
+  { 'InvalidProfileField' : { 'field' : string } } |
+  { 'ContentTooShort' : { 'min_length' : number } } |
+  { 'NotVibeOwner' : null } |
+  { 'NotFollowing' : null } |
+  { 'AccountNotFlagged' : null } |
+  { 'NotCollaborator' : null } |
+  { 'InvalidParentComment' : null } |
+  { 'InvalidEdition' : null } |
+  { 'VibeListed' : null } |
+  { 'BookmarkNotFound' : null } |
+  { 'Unauthorized' : { 'required' : Role } } |
+  { 'CommentNotFound' : null } |
+  { 'InvalidTipAmount' : null } |
+  { 'EditionExists' : null } |
+  { 'HandleTaken' : null } |
+  {
+    'RateLimited' : {
+      'action' : RateLimitedAction,
+      'retry_after_secs' : bigint,
+    }
+  } |
+  { 'InvalidMintPricing' : null } |
+  { 'AlreadyInCollection' : null } |
+  { 'NotInCollection' : null } |
+  { 'EditTooLarge' : null } |
+  { 'CannotTipOwnVibe' : null } |
+  { 'InvalidFolderName' : null } |
+  { 'InvalidContentRules' : null } |
+  { 'AppealPending' : null } |
+  { 'AlreadyListed' : null } |
+  { 'FolderNotFound' : null } |
+  { 'BlockedContent' : null } |
+  { 'CannotReportOwnVibe' : null } |
+  { 'EditLimitReached' : null } |
+  { 'AppealNotFound' : null } |
+  { 'AuctionNotFound' : null } |
+  { 'BidTooLow' : { 'min_bid' : bigint } } |
+  { 'CollectionLimitReached' : null } |
+  { 'EditUnchanged' : null } |
+  { 'AuctionHasBids' : null } |
+  { 'EditionNotFound' : null } |
+  { 'AnonymousCaller' : null } |
+  { 'ListingNotFound' : null };
+export type Visibility = { 'Visible' : null } |
+  { 'Hidden' : null } |
+  { 'Removed' : null };
+export interface Warning {
+  'moderator' : Principal,
+  'message' : string,
   'timestamp' : bigint,
+  'vibe_id' : string,
 }
 export interface _SERVICE {
+  'accept_collab' : ActorMethod<[bigint], Result>,
+  'add_comment' : ActorMethod<[string, string, [] | [bigint]], Result_1>,
+  'add_to_collection' : ActorMethod<[bigint, string, [] | [number]], Result_2>,
+  'appeal_vibe' : ActorMethod<[string, string], Result_2>,
+  'audit_supply' : ActorMethod<[], SupplyAudit>,
+  'autocomplete_tags' : ActorMethod<[string, number], Array<TagCount>>,
+  'bookmark_vibe' : ActorMethod<[string, [] | [string]], Result_1>,
+  'burn_vibe' : ActorMethod<[string], Result_1>,
+  'buy_edition_copy' : ActorMethod<[bigint], Result_3>,
+  'buy_vibe' : ActorMethod<[string], Result_4>,
+  'cancel_auction' : ActorMethod<[bigint], Result_2>,
+  'circulating_supply' : ActorMethod<[], bigint>,
   'claim_staking_rewards' : ActorMethod<[], bigint>,
+  'collect_edition' : ActorMethod<[string], Result_3>,
+  'create_auction' : ActorMethod<
+    [string, AuctionKind, bigint, bigint],
+    Result_1
+  >,
+  'create_bookmark_folder' : ActorMethod<[string], Result_2>,
+  'create_collection' : ActorMethod<[string, string], Result_1>,
+  'decline_collab' : ActorMethod<[bigint], Result_2>,
+  'delete_bookmark_folder' : ActorMethod<[string], Result_2>,
+  'delete_collection' : ActorMethod<[bigint], Result_2>,
+  'delete_comment' : ActorMethod<[bigint], Result_2>,
+  'delist_edition_copy' : ActorMethod<[bigint], Result_2>,
+  'delist_vibe' : ActorMethod<[string], Result_2>,
+  'edit_comment' : ActorMethod<[bigint, string], Result_2>,
+  'edit_vibe' : ActorMethod<[string, string], Result_1>,
+  'follow' : ActorMethod<[Principal], Result_2>,
+  'get_auction' : ActorMethod<[bigint], [] | [Auction]>,
+  'get_auction_min_bid' : ActorMethod<[bigint], Result_1>,
+  'get_collab_proposal' : ActorMethod<[bigint], Result_5>,
+  'get_collection' : ActorMethod<[bigint], Result_6>,
+  'get_collection_metadata' : ActorMethod<[bigint], Result_7>,
+  'get_collusion_clusters' : ActorMethod<[], Result_8>,
+  'get_comments' : ActorMethod<
+    [string, [] | [bigint], [] | [bigint], number],
+    CommentPage
+  >,
+  'get_content_limits' : ActorMethod<[], ContentLimits>,
+  'get_content_rules' : ActorMethod<[], Result_9>,
+  'get_creator_collections' : ActorMethod<
+    [Principal],
+    Array<[Collection, CollectionStats]>
+  >,
+  'get_creator_tip_stats' : ActorMethod<[Principal], TipStats>,
+  'get_edition_copy' : ActorMethod<[bigint], [] | [EditionCopy]>,
+  'get_edition_info' : ActorMethod<[string], [] | [EditionInfo]>,
+  'get_flagged_accounts' : ActorMethod<[], Result_10>,
+  'get_follow_counts' : ActorMethod<[Principal], FollowCounts>,
+  'get_followers' : ActorMethod<
+    [Principal, [] | [Principal], number],
+    PrincipalPage
+  >,
+  'get_following' : ActorMethod<
+    [Principal, [] | [Principal], number],
+    PrincipalPage
+  >,
+  'get_following_feed' : ActorMethod<[[] | [FeedCursor], number], FeedPage>,
   'get_leaderboard' : ActorMethod<[], Leaderboard>,
+  'get_lineage' : ActorMethod<[string], Result_11>,
+  'get_listings' : ActorMethod<
+    [ListingFilter, ListingSort, [] | [number], number],
+    ListingPage
+  >,
+  'get_mint_pricing' : ActorMethod<[], MintPricing>,
+  'get_moderation_queue' : ActorMethod<[], Result_12>,
   'get_my_balance' : ActorMethod<[], bigint>,
+  'get_my_bookmark_folders' : ActorMethod<[], Array<BookmarkFolder>>,
+  'get_my_bookmarks' : ActorMethod<
+    [[] | [string], [] | [bigint], number],
+    BookmarkPage
+  >,
+  'get_my_collab_proposals' : ActorMethod<[], Array<CollabProposal>>,
+  'get_my_editions' : ActorMethod<[], Array<EditionCopy>>,
+  'get_my_owned_vibes' : ActorMethod<[], Array<Vibe>>,
+  'get_my_quota' : ActorMethod<[], Array<Quota>>,
   'get_my_reputation' : ActorMethod<[], number>,
+  'get_my_roles' : ActorMethod<[], Array<Role>>,
+  'get_my_royalty_earnings' : ActorMethod<[], RoyaltyReport>,
   'get_my_vibes' : ActorMethod<[], Array<Vibe>>,
+  'get_my_warnings' : ActorMethod<[], Array<Warning>>,
+  'get_notifications' : ActorMethod<[[] | [bigint], number], NotificationPage>,
+  'get_open_auctions' : ActorMethod<[[] | [bigint], number], AuctionPage>,
+  'get_pending_appeals' : ActorMethod<[], Result_13>,
+  'get_profile' : ActorMethod<[Principal], [] | [Profile]>,
+  'get_profile_by_handle' : ActorMethod<[string], [] | [Profile]>,
+  'get_rate_limits' : ActorMethod<[], Array<[RateLimitedAction, RateLimit]>>,
+  'get_reactions' : ActorMethod<[string], ReactionSummary>,
+  'get_recent_tips' : ActorMethod<[string, number], Array<Tip>>,
+  'get_remix_upstream_share' : ActorMethod<[], number>,
+  'get_roles' : ActorMethod<[Principal], Array<Role>>,
+  'get_sale_history' : ActorMethod<[string], Array<Sale>>,
+  'get_tombstone' : ActorMethod<[string], [] | [Tombstone]>,
+  'get_trending_tags' : ActorMethod<[number], Array<TagCount>>,
+  'get_trending_vibes' : ActorMethod<[number], Array<Vibe>>,
+  'get_unread_count' : ActorMethod<[], bigint>,
+  'get_vibe_history' : ActorMethod<[string], Result_14>,
   'get_vibe_stats' : ActorMethod<[string], [bigint, bigint]>,
-  'like_vibe' : ActorMethod<[string], bigint>,
-  'mint_vibe' : ActorMethod<[string], string>,
+  'get_vibe_tip_stats' : ActorMethod<[string], TipStats>,
+  'get_vibes_by_tag' : ActorMethod<
+    [string, [] | [FeedCursor], number],
+    FeedPage
+  >,
+  'grant_role' : ActorMethod<[Principal, Role], Result_2>,
+  'is_following' : ActorMethod<[Principal, Principal], boolean>,
+  'is_handle_available' : ActorMethod<[string], boolean>,
+  'like_vibe' : ActorMethod<[string], Result_1>,
+  'list_edition_copy' : ActorMethod<[bigint, bigint], Result_2>,
+  'list_role_holders' : ActorMethod<[Role], Result_15>,
+  'list_vibe' : ActorMethod<[string, bigint], Result_2>,
+  'mark_notifications_read' : ActorMethod<[[] | [bigint]], bigint>,
+  'mint_vibe' : ActorMethod<[string, [] | [MintOptions]], Result_16>,
+  'moderate_comment' : ActorMethod<[bigint, ModerationAction], Result_2>,
+  'moderate_vibe' : ActorMethod<[string, ModerationAction], Result_2>,
+  'move_bookmark' : ActorMethod<[string, [] | [string]], Result_2>,
+  'open_edition' : ActorMethod<[string, bigint, number], Result_17>,
+  'place_bid' : ActorMethod<[bigint, bigint], Result_18>,
+  'propose_collab_vibe' : ActorMethod<
+    [string, Array<CreatorShare>, [] | [MintOptions]],
+    Result_1
+  >,
+  'quote_mint_cost' : ActorMethod<[], bigint>,
+  'react_vibe' : ActorMethod<[string, Reaction], Result_1>,
+  'release_handle' : ActorMethod<[string], Result_2>,
+  'remove_from_collection' : ActorMethod<[bigint, string], Result_2>,
+  'reorder_collection' : ActorMethod<[bigint, Array<string>], Result_2>,
+  'report_vibe' : ActorMethod<[string, string], Result_1>,
+  'reserve_handle' : ActorMethod<[string], Result_2>,
   'reset_account' : ActorMethod<[], undefined>,
-  'share_vibe' : ActorMethod<[string], bigint>,
+  'resolve_appeal' : ActorMethod<[string, boolean], Result_2>,
+  'review_flagged_account' : ActorMethod<[Principal, boolean], Result_2>,
+  'revoke_role' : ActorMethod<[Principal, Role], Result_2>,
+  'search_vibes' : ActorMethod<[string, [] | [number], number], SearchPage>,
+  'set_content_rules' : ActorMethod<[ContentRules], Result_2>,
+  'set_handle' : ActorMethod<[string], Result_16>,
+  'set_mint_pricing' : ActorMethod<[MintPricing], Result_2>,
+  'set_rate_limit' : ActorMethod<[RateLimitedAction, RateLimit], Result_2>,
+  'set_remix_upstream_share' : ActorMethod<[number], Result_2>,
+  'settle_auction' : ActorMethod<[bigint], Result_18>,
+  'share_vibe' : ActorMethod<[string], Result_1>,
   'stake_tokens' : ActorMethod<[bigint], undefined>,
+  'tip_vibe' : ActorMethod<[string, bigint, [] | [string]], Result_1>,
+  'total_supply' : ActorMethod<[], bigint>,
+  'transfer_edition' : ActorMethod<[bigint, Principal], Result_2>,
+  'treasury_balance' : ActorMethod<[], bigint>,
+  'unbookmark_vibe' : ActorMethod<[string], Result_1>,
+  'unfollow' : ActorMethod<[Principal], Result_2>,
+  'unreact_vibe' : ActorMethod<[string, Reaction], Result_1>,
+  'update_collection' : ActorMethod<
+    [bigint, string, string, [] | [string]],
+    Result_2
+  >,
+  'update_profile' : ActorMethod<[ProfileUpdate], Result_19>,
+  'warn_creator' : ActorMethod<[string, string], Result_2>,
 }
 export declare const idlFactory: IDL.InterfaceFactory;
 export declare const init: (args: { IDL: typeof IDL }) => IDL.Type[];
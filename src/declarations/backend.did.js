@@ -1,29 +1,775 @@
 export const idlFactory = ({ IDL }) => {
-  const Leaderboard = IDL.Record({
-    'top_creators' : IDL.Vec(IDL.Tuple(IDL.Principal, IDL.Nat64)),
-    'most_liked' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Nat64)),
-    'most_shared' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Nat64)),
+  const LineageNode = IDL.Rec();
+  const InitArgs = IDL.Record({ 'admins' : IDL.Vec(IDL.Principal) });
+  const CollabStatus = IDL.Variant({
+    'Declined' : IDL.Null,
+    'Published' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const Role = IDL.Variant({
+    'Curator' : IDL.Null,
+    'Admin' : IDL.Null,
+    'Moderator' : IDL.Null,
+  });
+  const RateLimitedAction = IDL.Variant({
+    'Share' : IDL.Null,
+    'Like' : IDL.Null,
+    'Mint' : IDL.Null,
+    'Refill' : IDL.Null,
+    'Comment' : IDL.Null,
+    'React' : IDL.Null,
+  });
+  const VibeError = IDL.Variant({
+    'InvalidHandle' : IDL.Null,
+    'NothingToAppeal' : IDL.Null,
+    'LastAdmin' : IDL.Null,
+    'AlreadyReacted' : IDL.Null,
+    'CannotBuyOwnVibe' : IDL.Null,
+    'VibeHasEditions' : IDL.Null,
+    'CannotFollow' : IDL.Null,
+    'AuctionClosed' : IDL.Null,
+    'InvalidRateLimit' : IDL.Null,
+    'AlreadyFollowing' : IDL.Null,
+    'VibeNotFound' : IDL.Null,
+    'InvalidPrice' : IDL.Null,
+    'RemixTooDeep' : IDL.Null,
+    'InvalidRoyalty' : IDL.Record({ 'max_bps' : IDL.Nat16 }),
+    'MintCostExceeded' : IDL.Record({ 'quoted' : IDL.Nat64 }),
+    'InvalidCollabShares' : IDL.Null,
+    'InvalidAuction' : IDL.Null,
+    'NotCollectionOwner' : IDL.Null,
+    'HandleReserved' : IDL.Null,
+    'InvalidReason' : IDL.Null,
+    'AuctionNotEnded' : IDL.Null,
+    'EditionSoldOut' : IDL.Null,
+    'NotCommentAuthor' : IDL.Null,
+    'AlreadyReported' : IDL.Null,
+    'CollabNotFound' : IDL.Null,
+    'CollectionNotFound' : IDL.Null,
+    'InvalidRemixShare' : IDL.Record({ 'max_bps' : IDL.Nat16 }),
+    'InsufficientBalance' : IDL.Null,
+    'ContentTooLong' : IDL.Record({ 'max_length' : IDL.Nat32 }),
+    'FolderLimitReached' : IDL.Null,
+    'AlreadyBookmarked' : IDL.Null,
+    'ReactionNotFound' : IDL.Null,
+    'FolderExists' : IDL.Null,
+    'InvalidProfileField' : IDL.Record({ 'field' : IDL.Text }),
+    'ContentTooShort' : IDL.Record({ 'min_length' : IDL.Nat32 }),
+    'NotVibeOwner' : IDL.Null,
+    'NotFollowing' : IDL.Null,
+    'AccountNotFlagged' : IDL.Null,
+    'NotCollaborator' : IDL.Null,
+    'InvalidParentComment' : IDL.Null,
+    'InvalidEdition' : IDL.Null,
+    'VibeListed' : IDL.Null,
+    'BookmarkNotFound' : IDL.Null,
+    'Unauthorized' : IDL.Record({ 'required' : Role }),
+    'CommentNotFound' : IDL.Null,
+    'InvalidTipAmount' : IDL.Null,
+    'EditionExists' : IDL.Null,
+    'HandleTaken' : IDL.Null,
+    'RateLimited' : IDL.Record({
+      'action' : RateLimitedAction,
+      'retry_after_secs' : IDL.Nat64,
+    }),
+    'InvalidMintPricing' : IDL.Null,
+    'AlreadyInCollection' : IDL.Null,
+    'NotInCollection' : IDL.Null,
+    'EditTooLarge' : IDL.Null,
+    'CannotTipOwnVibe' : IDL.Null,
+    'InvalidFolderName' : IDL.Null,
+    'InvalidContentRules' : IDL.Null,
+    'AppealPending' : IDL.Null,
+    'AlreadyListed' : IDL.Null,
+    'FolderNotFound' : IDL.Null,
+    'BlockedContent' : IDL.Null,
+    'CannotReportOwnVibe' : IDL.Null,
+    'EditLimitReached' : IDL.Null,
+    'AppealNotFound' : IDL.Null,
+    'AuctionNotFound' : IDL.Null,
+    'BidTooLow' : IDL.Record({ 'min_bid' : IDL.Nat64 }),
+    'CollectionLimitReached' : IDL.Null,
+    'EditUnchanged' : IDL.Null,
+    'AuctionHasBids' : IDL.Null,
+    'EditionNotFound' : IDL.Null,
+    'AnonymousCaller' : IDL.Null,
+    'ListingNotFound' : IDL.Null,
+  });
+  const Result = IDL.Variant({ 'Ok' : CollabStatus, 'Err' : VibeError });
+  const Result_1 = IDL.Variant({ 'Ok' : IDL.Nat64, 'Err' : VibeError });
+  const Result_2 = IDL.Variant({ 'Ok' : IDL.Null, 'Err' : VibeError });
+  const SupplyAudit = IDL.Record({
+    'circulating_supply' : IDL.Nat64,
+    'escrowed' : IDL.Nat64,
+    'total_supply' : IDL.Nat64,
+    'treasury_balance' : IDL.Nat64,
+    'balanced' : IDL.Bool,
+    'balances' : IDL.Nat64,
+  });
+  const TagCount = IDL.Record({ 'tag' : IDL.Text, 'count' : IDL.Nat64 });
+  const EditionCopy = IDL.Record({
+    'token_id' : IDL.Nat64,
+    'owner' : IDL.Principal,
+    'number' : IDL.Nat32,
+    'supply' : IDL.Nat32,
+    'vibe_id' : IDL.Text,
+    'collected_at' : IDL.Nat64,
+    'list_price' : IDL.Opt(IDL.Nat64),
+  });
+  const Result_3 = IDL.Variant({ 'Ok' : EditionCopy, 'Err' : VibeError });
+  const Sale = IDL.Record({
+    'fee' : IDL.Nat64,
+    'seller' : IDL.Principal,
+    'timestamp' : IDL.Nat64,
+    'buyer' : IDL.Principal,
+    'price' : IDL.Nat64,
+    'royalty' : IDL.Nat64,
+    'vibe_id' : IDL.Text,
+  });
+  const Result_4 = IDL.Variant({ 'Ok' : Sale, 'Err' : VibeError });
+  const AuctionKind = IDL.Variant({
+    'Dutch' : IDL.Record({ 'start_price' : IDL.Nat64 }),
+    'English' : IDL.Null,
+  });
+  const AuctionStatus = IDL.Variant({
+    'Open' : IDL.Null,
+    'Sold' : IDL.Null,
+    'Unsold' : IDL.Null,
+    'Cancelled' : IDL.Null,
+  });
+  const Bid = IDL.Record({
+    'timestamp' : IDL.Nat64,
+    'amount' : IDL.Nat64,
+    'bidder' : IDL.Principal,
+  });
+  const Auction = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : AuctionStatus,
+    'reserve_price' : IDL.Nat64,
+    'bids' : IDL.Vec(Bid),
+    'ends_at' : IDL.Nat64,
+    'kind' : AuctionKind,
+    'seller' : IDL.Principal,
+    'vibe_id' : IDL.Text,
+    'started_at' : IDL.Nat64,
+  });
+  const CreatorShare = IDL.Record({
+    'creator' : IDL.Principal,
+    'share_bps' : IDL.Nat16,
+  });
+  const CollabProposal = IDL.Record({
+    'id' : IDL.Nat64,
+    'status' : CollabStatus,
+    'content' : IDL.Text,
+    'shares' : IDL.Vec(CreatorShare),
+    'created_at' : IDL.Nat64,
+    'mint_cost' : IDL.Nat64,
+    'proposer' : IDL.Principal,
+    'accepted' : IDL.Vec(IDL.Principal),
+    'vibe_id' : IDL.Opt(IDL.Text),
+  });
+  const Result_5 = IDL.Variant({ 'Ok' : CollabProposal, 'Err' : VibeError });
+  const Collection = IDL.Record({
+    'id' : IDL.Nat64,
+    'vibe_ids' : IDL.Vec(IDL.Text),
+    'title' : IDL.Text,
+    'updated_at' : IDL.Nat64,
+    'creator' : IDL.Principal,
+    'description' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'cover_vibe_id' : IDL.Opt(IDL.Text),
+  });
+  const Visibility = IDL.Variant({
+    'Visible' : IDL.Null,
+    'Hidden' : IDL.Null,
+    'Removed' : IDL.Null,
   });
   const Vibe = IDL.Record({
     'id' : IDL.Text,
     'creator' : IDL.Principal,
     'content' : IDL.Text,
     'shares' : IDL.Nat64,
+    'owner' : IDL.Principal,
+    'tags' : IDL.Vec(IDL.Text),
+    'edited_at' : IDL.Opt(IDL.Nat64),
+    'likes' : IDL.Nat64,
+    'parent_id' : IDL.Opt(IDL.Text),
+    'collaborators' : IDL.Vec(CreatorShare),
+    'timestamp' : IDL.Nat64,
+    'comments' : IDL.Nat64,
+    'visibility' : Visibility,
+    'royalty_bps' : IDL.Nat16,
+  });
+  const CollectionStats = IDL.Record({
+    'shares' : IDL.Nat64,
+    'vibes' : IDL.Nat64,
+    'likes' : IDL.Nat64,
+  });
+  const CollectionView = IDL.Record({
+    'collection' : Collection,
+    'vibes' : IDL.Vec(Vibe),
+    'stats' : CollectionStats,
+  });
+  const Result_6 = IDL.Variant({ 'Ok' : CollectionView, 'Err' : VibeError });
+  const MetadataValue = IDL.Variant({ 'Nat' : IDL.Nat, 'Text' : IDL.Text });
+  const Result_7 = IDL.Variant({
+    'Ok' : IDL.Vec(IDL.Tuple(IDL.Text, MetadataValue)),
+    'Err' : VibeError,
+  });
+  const CollusionCluster = IDL.Record({
+    'members' : IDL.Vec(IDL.Principal),
+    'mutual_interactions' : IDL.Nat64,
+  });
+  const Result_8 = IDL.Variant({
+    'Ok' : IDL.Vec(CollusionCluster),
+    'Err' : VibeError,
+  });
+  const Comment = IDL.Record({
+    'id' : IDL.Nat64,
+    'deleted' : IDL.Bool,
+    'text' : IDL.Text,
+    'created_at' : IDL.Nat64,
+    'edited_at' : IDL.Opt(IDL.Nat64),
+    'author' : IDL.Principal,
+    'parent_id' : IDL.Opt(IDL.Nat64),
+    'reply_count' : IDL.Nat64,
+    'visibility' : Visibility,
+    'vibe_id' : IDL.Text,
+  });
+  const CommentPage = IDL.Record({
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+    'comments' : IDL.Vec(Comment),
+  });
+  const ContentLimits = IDL.Record({
+    'min_length' : IDL.Nat32,
+    'max_length' : IDL.Nat32,
+  });
+  const ContentRules = IDL.Record({
+    'min_length' : IDL.Nat32,
+    'blocked_patterns' : IDL.Vec(IDL.Text),
+    'blocked_words' : IDL.Vec(IDL.Text),
+    'max_length' : IDL.Nat32,
+  });
+  const Result_9 = IDL.Variant({ 'Ok' : ContentRules, 'Err' : VibeError });
+  const TipStats = IDL.Record({ 'total' : IDL.Nat64, 'count' : IDL.Nat64 });
+  const EditionInfo = IDL.Record({
+    'creator' : IDL.Principal,
+    'opened_at' : IDL.Nat64,
+    'minted' : IDL.Nat32,
+    'supply' : IDL.Nat32,
+    'sold_out' : IDL.Bool,
+    'price' : IDL.Nat64,
+    'vibe_id' : IDL.Text,
+  });
+  const FlagStatus = IDL.Variant({
+    'PendingReview' : IDL.Null,
+    'Confirmed' : IDL.Null,
+    'Cleared' : IDL.Null,
+  });
+  const FlaggedAccount = IDL.Record({
+    'status' : FlagStatus,
+    'principal' : IDL.Principal,
+    'partners' : IDL.Vec(IDL.Principal),
+    'flagged_at' : IDL.Nat64,
+  });
+  const Result_10 = IDL.Variant({
+    'Ok' : IDL.Vec(FlaggedAccount),
+    'Err' : VibeError,
+  });
+  const FollowCounts = IDL.Record({
+    'followers' : IDL.Nat64,
+    'following' : IDL.Nat64,
+  });
+  const PrincipalPage = IDL.Record({
+    'next_cursor' : IDL.Opt(IDL.Principal),
+    'principals' : IDL.Vec(IDL.Principal),
+  });
+  const FeedCursor = IDL.Record({
+    'timestamp' : IDL.Nat64,
+    'vibe_id' : IDL.Text,
+  });
+  const FeedPage = IDL.Record({
+    'vibes' : IDL.Vec(Vibe),
+    'next_cursor' : IDL.Opt(FeedCursor),
+  });
+  const ProfileSummary = IDL.Record({
+    'principal' : IDL.Principal,
+    'display_name' : IDL.Opt(IDL.Text),
+    'handle' : IDL.Opt(IDL.Text),
+    'avatar' : IDL.Opt(IDL.Text),
+  });
+  const Leaderboard = IDL.Record({
+    'top_creators' : IDL.Vec(IDL.Tuple(IDL.Principal, IDL.Nat64)),
+    'most_liked' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Nat64)),
+    'most_shared' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Nat64)),
+    'most_tipped' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Nat64)),
+    'profiles' : IDL.Vec(ProfileSummary),
+  });
+  LineageNode.fill(
+    IDL.Record({
+      'vibe' : IDL.Opt(Vibe),
+      'children' : IDL.Vec(LineageNode),
+      'vibe_id' : IDL.Text,
+    })
+  );
+  const Lineage = IDL.Record({
+    'root' : LineageNode,
+    'ancestors' : IDL.Vec(IDL.Text),
+  });
+  const Result_11 = IDL.Variant({ 'Ok' : Lineage, 'Err' : VibeError });
+  const ListingFilter = IDL.Record({
+    'tag' : IDL.Opt(IDL.Text),
+    'creator' : IDL.Opt(IDL.Principal),
+    'seller' : IDL.Opt(IDL.Principal),
+    'max_price' : IDL.Opt(IDL.Nat64),
+    'min_price' : IDL.Opt(IDL.Nat64),
+  });
+  const ListingSort = IDL.Variant({
+    'PriceDescending' : IDL.Null,
+    'MostLiked' : IDL.Null,
+    'Newest' : IDL.Null,
+    'PriceAscending' : IDL.Null,
+  });
+  const Listing = IDL.Record({
+    'seller' : IDL.Principal,
+    'price' : IDL.Nat64,
+    'vibe_id' : IDL.Text,
+    'listed_at' : IDL.Nat64,
+  });
+  const ListedVibe = IDL.Record({ 'listing' : Listing, 'vibe' : Vibe });
+  const ListingPage = IDL.Record({
+    'listings' : IDL.Vec(ListedVibe),
+    'next_cursor' : IDL.Opt(IDL.Nat32),
+  });
+  const MintPricing = IDL.Variant({
+    'Flat' : IDL.Record({ 'price' : IDL.Nat64 }),
+    'BondingCurve' : IDL.Record({
+      'base' : IDL.Nat64,
+      'step' : IDL.Nat64,
+      'every' : IDL.Nat64,
+    }),
+    'Escalating' : IDL.Record({ 'base' : IDL.Nat64, 'step' : IDL.Nat64 }),
+  });
+  const Report = IDL.Record({
+    'timestamp' : IDL.Nat64,
+    'reporter' : IDL.Principal,
+    'reason' : IDL.Text,
+  });
+  const QueueEntry = IDL.Record({ 'vibe' : Vibe, 'reports' : IDL.Vec(Report) });
+  const Result_12 = IDL.Variant({
+    'Ok' : IDL.Vec(QueueEntry),
+    'Err' : VibeError,
+  });
+  const BookmarkFolder = IDL.Record({ 'name' : IDL.Text, 'count' : IDL.Nat64 });
+  const SavedVibe = IDL.Record({
+    'saved_at' : IDL.Nat64,
+    'vibe' : Vibe,
+    'bookmark_id' : IDL.Nat64,
+    'folder' : IDL.Opt(IDL.Text),
+  });
+  const BookmarkPage = IDL.Record({
+    'bookmarks' : IDL.Vec(SavedVibe),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const Quota = IDL.Record({
+    'action' : RateLimitedAction,
+    'next_refill_in' : IDL.Nat64,
+    'remaining' : IDL.Nat32,
+    'capacity' : IDL.Nat32,
+  });
+  const RoyaltyPayment = IDL.Record({
+    'seller' : IDL.Principal,
+    'sale_price' : IDL.Nat64,
+    'timestamp' : IDL.Nat64,
+    'buyer' : IDL.Principal,
+    'amount' : IDL.Nat64,
+    'vibe_id' : IDL.Text,
+  });
+  const RoyaltyReport = IDL.Record({
+    'payments' : IDL.Vec(RoyaltyPayment),
+    'sales' : IDL.Nat64,
+    'total_earned' : IDL.Nat64,
+  });
+  const Warning = IDL.Record({
+    'moderator' : IDL.Principal,
+    'message' : IDL.Text,
+    'timestamp' : IDL.Nat64,
+    'vibe_id' : IDL.Text,
+  });
+  const Reaction = IDL.Variant({
+    'Sad' : IDL.Null,
+    'Wow' : IDL.Null,
+    'Laugh' : IDL.Null,
+    'Clap' : IDL.Null,
+    'Fire' : IDL.Null,
+    'Love' : IDL.Null,
+  });
+  const NotificationKind = IDL.Variant({
+    'Tip' : IDL.Record({ 'amount' : IDL.Nat64, 'vibe_id' : IDL.Text }),
+    'Share' : IDL.Record({ 'vibe_id' : IDL.Text }),
+    'Follow' : IDL.Null,
+    'Like' : IDL.Record({ 'vibe_id' : IDL.Text }),
+    'Outbid' : IDL.Record({ 'auction_id' : IDL.Nat64 }),
+    'Comment' : IDL.Record({ 'vibe_id' : IDL.Text, 'comment_id' : IDL.Nat64 }),
+    'Remix' : IDL.Record({ 'remix_id' : IDL.Text, 'vibe_id' : IDL.Text }),
+    'Mention' : IDL.Record({
+      'vibe_id' : IDL.Text,
+      'comment_id' : IDL.Opt(IDL.Nat64),
+    }),
+    'CollabInvite' : IDL.Record({ 'proposal_id' : IDL.Nat64 }),
+    'Reaction' : IDL.Record({ 'vibe_id' : IDL.Text, 'reaction' : Reaction }),
+  });
+  const Notification = IDL.Record({
+    'id' : IDL.Nat64,
+    'actor' : IDL.Principal,
+    'kind' : NotificationKind,
+    'read' : IDL.Bool,
+    'timestamp' : IDL.Nat64,
+  });
+  const NotificationPage = IDL.Record({
+    'notifications' : IDL.Vec(Notification),
+    'unread' : IDL.Nat64,
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const AuctionPage = IDL.Record({
+    'auctions' : IDL.Vec(Auction),
+    'next_cursor' : IDL.Opt(IDL.Nat64),
+  });
+  const AppealStatus = IDL.Variant({
+    'Rejected' : IDL.Null,
+    'Accepted' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const Appeal = IDL.Record({
+    'status' : AppealStatus,
+    'creator' : IDL.Principal,
+    'statement' : IDL.Text,
+    'timestamp' : IDL.Nat64,
+    'vibe_id' : IDL.Text,
+  });
+  const Result_13 = IDL.Variant({ 'Ok' : IDL.Vec(Appeal), 'Err' : VibeError });
+  const Profile = IDL.Record({
+    'bio' : IDL.Opt(IDL.Text),
+    'updated_at' : IDL.Nat64,
+    'principal' : IDL.Principal,
+    'links' : IDL.Vec(IDL.Text),
+    'display_name' : IDL.Opt(IDL.Text),
+    'handle' : IDL.Opt(IDL.Text),
+    'avatar' : IDL.Opt(IDL.Text),
+  });
+  const RateLimit = IDL.Record({
+    'refill_secs' : IDL.Nat64,
+    'capacity' : IDL.Nat32,
+  });
+  const ReactionCount = IDL.Record({
+    'count' : IDL.Nat64,
+    'reaction' : Reaction,
+  });
+  const ReactionSummary = IDL.Record({
+    'mine' : IDL.Vec(Reaction),
     'likes' : IDL.Nat64,
+    'reactions' : IDL.Vec(ReactionCount),
+  });
+  const Tip = IDL.Record({
+    'tipper' : IDL.Principal,
+    'memo' : IDL.Opt(IDL.Text),
     'timestamp' : IDL.Nat64,
+    'amount' : IDL.Nat64,
   });
+  const Tombstone = IDL.Record({
+    'creator' : IDL.Principal,
+    'burned_at' : IDL.Nat64,
+    'vibe_id' : IDL.Text,
+    'minted_at' : IDL.Nat64,
+    'refund' : IDL.Nat64,
+  });
+  const Revision = IDL.Record({
+    'content' : IDL.Text,
+    'timestamp' : IDL.Nat64,
+  });
+  const Result_14 = IDL.Variant({
+    'Ok' : IDL.Vec(Revision),
+    'Err' : VibeError,
+  });
+  const Result_15 = IDL.Variant({
+    'Ok' : IDL.Vec(IDL.Principal),
+    'Err' : VibeError,
+  });
+  const MintOptions = IDL.Record({
+    'max_cost' : IDL.Opt(IDL.Nat64),
+    'remix_of' : IDL.Opt(IDL.Text),
+    'royalty_bps' : IDL.Opt(IDL.Nat16),
+  });
+  const Result_16 = IDL.Variant({ 'Ok' : IDL.Text, 'Err' : VibeError });
+  const ModerationAction = IDL.Variant({
+    'Hide' : IDL.Null,
+    'Restore' : IDL.Null,
+    'Remove' : IDL.Null,
+    'Dismiss' : IDL.Null,
+  });
+  const Result_17 = IDL.Variant({ 'Ok' : EditionInfo, 'Err' : VibeError });
+  const Result_18 = IDL.Variant({ 'Ok' : AuctionStatus, 'Err' : VibeError });
+  const SearchHit = IDL.Record({ 'vibe' : Vibe, 'score' : IDL.Float64 });
+  const SearchPage = IDL.Record({
+    'hits' : IDL.Vec(SearchHit),
+    'next_cursor' : IDL.Opt(IDL.Nat32),
+  });
+  const ProfileUpdate = IDL.Record({
+    'bio' : IDL.Opt(IDL.Text),
+    'links' : IDL.Vec(IDL.Text),
+    'display_name' : IDL.Opt(IDL.Text),
+    'avatar' : IDL.Opt(IDL.Text),
+  });
+  const Result_19 = IDL.Variant({ 'Ok' : Profile, 'Err' : VibeError });
   return IDL.Service({
+    'accept_collab' : IDL.Func([IDL.Nat64], [Result], []),
+    'add_comment' : IDL.Func(
+        [IDL.Text, IDL.Text, IDL.Opt(IDL.Nat64)],
+        [Result_1],
+        [],
+      ),
+    'add_to_collection' : IDL.Func(
+        [IDL.Nat64, IDL.Text, IDL.Opt(IDL.Nat32)],
+        [Result_2],
+        [],
+      ),
+    'appeal_vibe' : IDL.Func([IDL.Text, IDL.Text], [Result_2], []),
+    'audit_supply' : IDL.Func([], [SupplyAudit], ['query']),
+    'autocomplete_tags' : IDL.Func(
+        [IDL.Text, IDL.Nat32],
+        [IDL.Vec(TagCount)],
+        ['query'],
+      ),
+    'bookmark_vibe' : IDL.Func([IDL.Text, IDL.Opt(IDL.Text)], [Result_1], []),
+    'burn_vibe' : IDL.Func([IDL.Text], [Result_1], []),
+    'buy_edition_copy' : IDL.Func([IDL.Nat64], [Result_3], []),
+    'buy_vibe' : IDL.Func([IDL.Text], [Result_4], []),
+    'cancel_auction' : IDL.Func([IDL.Nat64], [Result_2], []),
+    'circulating_supply' : IDL.Func([], [IDL.Nat64], ['query']),
     'claim_staking_rewards' : IDL.Func([], [IDL.Nat64], []),
+    'collect_edition' : IDL.Func([IDL.Text], [Result_3], []),
+    'create_auction' : IDL.Func(
+        [IDL.Text, AuctionKind, IDL.Nat64, IDL.Nat64],
+        [Result_1],
+        [],
+      ),
+    'create_bookmark_folder' : IDL.Func([IDL.Text], [Result_2], []),
+    'create_collection' : IDL.Func([IDL.Text, IDL.Text], [Result_1], []),
+    'decline_collab' : IDL.Func([IDL.Nat64], [Result_2], []),
+    'delete_bookmark_folder' : IDL.Func([IDL.Text], [Result_2], []),
+    'delete_collection' : IDL.Func([IDL.Nat64], [Result_2], []),
+    'delete_comment' : IDL.Func([IDL.Nat64], [Result_2], []),
+    'delist_edition_copy' : IDL.Func([IDL.Nat64], [Result_2], []),
+    'delist_vibe' : IDL.Func([IDL.Text], [Result_2], []),
+    'edit_comment' : IDL.Func([IDL.Nat64, IDL.Text], [Result_2], []),
+    'edit_vibe' : IDL.Func([IDL.Text, IDL.Text], [Result_1], []),
+    'follow' : IDL.Func([IDL.Principal], [Result_2], []),
+    'get_auction' : IDL.Func([IDL.Nat64], [IDL.Opt(Auction)], ['query']),
+    'get_auction_min_bid' : IDL.Func([IDL.Nat64], [Result_1], ['query']),
+    'get_collab_proposal' : IDL.Func([IDL.Nat64], [Result_5], ['query']),
+    'get_collection' : IDL.Func([IDL.Nat64], [Result_6], ['query']),
+    'get_collection_metadata' : IDL.Func([IDL.Nat64], [Result_7], ['query']),
+    'get_collusion_clusters' : IDL.Func([], [Result_8], ['query']),
+    'get_comments' : IDL.Func(
+        [IDL.Text, IDL.Opt(IDL.Nat64), IDL.Opt(IDL.Nat64), IDL.Nat32],
+        [CommentPage],
+        ['query'],
+      ),
+    'get_content_limits' : IDL.Func([], [ContentLimits], ['query']),
+    'get_content_rules' : IDL.Func([], [Result_9], ['query']),
+    'get_creator_collections' : IDL.Func(
+        [IDL.Principal],
+        [IDL.Vec(IDL.Tuple(Collection, CollectionStats))],
+        ['query'],
+      ),
+    'get_creator_tip_stats' : IDL.Func([IDL.Principal], [TipStats], ['query']),
+    'get_edition_copy' : IDL.Func(
+        [IDL.Nat64],
+        [IDL.Opt(EditionCopy)],
+        ['query'],
+      ),
+    'get_edition_info' : IDL.Func(
+        [IDL.Text],
+        [IDL.Opt(EditionInfo)],
+        ['query'],
+      ),
+    'get_flagged_accounts' : IDL.Func([], [Result_10], ['query']),
+    'get_follow_counts' : IDL.Func([IDL.Principal], [FollowCounts], ['query']),
+    'get_followers' : IDL.Func(
+        [IDL.Principal, IDL.Opt(IDL.Principal), IDL.Nat32],
+        [PrincipalPage],
+        ['query'],
+      ),
+    'get_following' : IDL.Func(
+        [IDL.Principal, IDL.Opt(IDL.Principal), IDL.Nat32],
+        [PrincipalPage],
+        ['query'],
+      ),
+    'get_following_feed' : IDL.Func(
+        [IDL.Opt(FeedCursor), IDL.Nat32],
+        [FeedPage],
+        ['query'],
+      ),
     'get_leaderboard' : IDL.Func([], [Leaderboard], ['query']),
+    'get_lineage' : IDL.Func([IDL.Text], [Result_11], ['query']),
+    'get_listings' : IDL.Func(
+        [ListingFilter, ListingSort, IDL.Opt(IDL.Nat32), IDL.Nat32],
+        [ListingPage],
+        ['query'],
+      ),
+    'get_mint_pricing' : IDL.Func([], [MintPricing], ['query']),
+    'get_moderation_queue' : IDL.Func([], [Result_12], ['query']),
     'get_my_balance' : IDL.Func([], [IDL.Nat64], ['query']),
+    'get_my_bookmark_folders' : IDL.Func(
+        [],
+        [IDL.Vec(BookmarkFolder)],
+        ['query'],
+      ),
+    'get_my_bookmarks' : IDL.Func(
+        [IDL.Opt(IDL.Text), IDL.Opt(IDL.Nat64), IDL.Nat32],
+        [BookmarkPage],
+        ['query'],
+      ),
+    'get_my_collab_proposals' : IDL.Func(
+        [],
+        [IDL.Vec(CollabProposal)],
+        ['query'],
+      ),
+    'get_my_editions' : IDL.Func([], [IDL.Vec(EditionCopy)], ['query']),
+    'get_my_owned_vibes' : IDL.Func([], [IDL.Vec(Vibe)], ['query']),
+    'get_my_quota' : IDL.Func([], [IDL.Vec(Quota)], ['query']),
     'get_my_reputation' : IDL.Func([], [IDL.Float32], ['query']),
+    'get_my_roles' : IDL.Func([], [IDL.Vec(Role)], ['query']),
+    'get_my_royalty_earnings' : IDL.Func([], [RoyaltyReport], ['query']),
     'get_my_vibes' : IDL.Func([], [IDL.Vec(Vibe)], ['query']),
+    'get_my_warnings' : IDL.Func([], [IDL.Vec(Warning)], ['query']),
+    'get_notifications' : IDL.Func(
+        [IDL.Opt(IDL.Nat64), IDL.Nat32],
+        [NotificationPage],
+        ['query'],
+      ),
+    'get_open_auctions' : IDL.Func(
+        [IDL.Opt(IDL.Nat64), IDL.Nat32],
+        [AuctionPage],
+        ['query'],
+      ),
+    'get_pending_appeals' : IDL.Func([], [Result_13], ['query']),
+    'get_profile' : IDL.Func([IDL.Principal], [IDL.Opt(Profile)], ['query']),
+    'get_profile_by_handle' : IDL.Func(
+        [IDL.Text],
+        [IDL.Opt(Profile)],
+        ['query'],
+      ),
+    'get_rate_limits' : IDL.Func(
+        [],
+        [IDL.Vec(IDL.Tuple(RateLimitedAction, RateLimit))],
+        ['query'],
+      ),
+    'get_reactions' : IDL.Func([IDL.Text], [ReactionSummary], ['query']),
+    'get_recent_tips' : IDL.Func(
+        [IDL.Text, IDL.Nat32],
+        [IDL.Vec(Tip)],
+        ['query'],
+      ),
+    'get_remix_upstream_share' : IDL.Func([], [IDL.Nat16], ['query']),
+    'get_roles' : IDL.Func([IDL.Principal], [IDL.Vec(Role)], ['query']),
+    'get_sale_history' : IDL.Func([IDL.Text], [IDL.Vec(Sale)], ['query']),
+    'get_tombstone' : IDL.Func([IDL.Text], [IDL.Opt(Tombstone)], ['query']),
+    'get_trending_tags' : IDL.Func([IDL.Nat32], [IDL.Vec(TagCount)], ['query']),
+    'get_trending_vibes' : IDL.Func([IDL.Nat32], [IDL.Vec(Vibe)], ['query']),
+    'get_unread_count' : IDL.Func([], [IDL.Nat64], ['query']),
+    'get_vibe_history' : IDL.Func([IDL.Text], [Result_14], ['query']),
     'get_vibe_stats' : IDL.Func([IDL.Text], [IDL.Nat64, IDL.Nat64], ['query']),
-    'like_vibe' : IDL.Func([IDL.Text], [IDL.Nat64], []),
-    'mint_vibe' : IDL.Func([IDL.Text], [IDL.Text], []),
+    'get_vibe_tip_stats' : IDL.Func([IDL.Text], [TipStats], ['query']),
+    'get_vibes_by_tag' : IDL.Func(
+        [IDL.Text, IDL.Opt(FeedCursor), IDL.Nat32],
+        [FeedPage],
+        ['query'],
+      ),
+    'grant_role' : IDL.Func([IDL.Principal, Role], [Result_2], []),
+    'is_following' : IDL.Func(
+        [IDL.Principal, IDL.Principal],
+        [IDL.Bool],
+        ['query'],
+      ),
+    'is_handle_available' : IDL.Func([IDL.Text], [IDL.Bool], ['query']),
+    'like_vibe' : IDL.Func([IDL.Text], [Result_1], []),
+    'list_edition_copy' : IDL.Func([IDL.Nat64, IDL.Nat64], [Result_2], []),
+    'list_role_holders' : IDL.Func([Role], [Result_15], ['query']),
+    'list_vibe' : IDL.Func([IDL.Text, IDL.Nat64], [Result_2], []),
+    'mark_notifications_read' : IDL.Func([IDL.Opt(IDL.Nat64)], [IDL.Nat64], []),
+    'mint_vibe' : IDL.Func([IDL.Text, IDL.Opt(MintOptions)], [Result_16], []),
+    'moderate_comment' : IDL.Func(
+        [IDL.Nat64, ModerationAction],
+        [Result_2],
+        [],
+      ),
+    'moderate_vibe' : IDL.Func([IDL.Text, ModerationAction], [Result_2], []),
+    'move_bookmark' : IDL.Func([IDL.Text, IDL.Opt(IDL.Text)], [Result_2], []),
+    'open_edition' : IDL.Func(
+        [IDL.Text, IDL.Nat64, IDL.Nat32],
+        [Result_17],
+        [],
+      ),
+    'place_bid' : IDL.Func([IDL.Nat64, IDL.Nat64], [Result_18], []),
+    'propose_collab_vibe' : IDL.Func(
+        [IDL.Text, IDL.Vec(CreatorShare), IDL.Opt(MintOptions)],
+        [Result_1],
+        [],
+      ),
+    'quote_mint_cost' : IDL.Func([], [IDL.Nat64], ['query']),
+    'react_vibe' : IDL.Func([IDL.Text, Reaction], [Result_1], []),
+    'release_handle' : IDL.Func([IDL.Text], [Result_2], []),
+    'remove_from_collection' : IDL.Func([IDL.Nat64, IDL.Text], [Result_2], []),
+    'reorder_collection' : IDL.Func(
+        [IDL.Nat64, IDL.Vec(IDL.Text)],
+        [Result_2],
+        [],
+      ),
+    'report_vibe' : IDL.Func([IDL.Text, IDL.Text], [Result_1], []),
+    'reserve_handle' : IDL.Func([IDL.Text], [Result_2], []),
     'reset_account' : IDL.Func([], [], []),
-    'share_vibe' : IDL.Func([IDL.Text], [IDL.Nat64], []),
+    'resolve_appeal' : IDL.Func([IDL.Text, IDL.Bool], [Result_2], []),
+    'review_flagged_account' : IDL.Func(
+        [IDL.Principal, IDL.Bool],
+        [Result_2],
+        [],
+      ),
+    'revoke_role' : IDL.Func([IDL.Principal, Role], [Result_2], []),
+    'search_vibes' : IDL.Func(
+        [IDL.Text, IDL.Opt(IDL.Nat32), IDL.Nat32],
+        [SearchPage],
+        ['query'],
+      ),
+    'set_content_rules' : IDL.Func([ContentRules], [Result_2], []),
+    'set_handle' : IDL.Func([IDL.Text], [Result_16], []),
+    'set_mint_pricing' : IDL.Func([MintPricing], [Result_2], []),
+    'set_rate_limit' : IDL.Func([RateLimitedAction, RateLimit], [Result_2], []),
+    'set_remix_upstream_share' : IDL.Func([IDL.Nat16], [Result_2], []),
+    'settle_auction' : IDL.Func([IDL.Nat64], [Result_18], []),
+    'share_vibe' : IDL.Func([IDL.Text], [Result_1], []),
     'stake_tokens' : IDL.Func([IDL.Nat64], [], []),
+    'tip_vibe' : IDL.Func(
+        [IDL.Text, IDL.Nat64, IDL.Opt(IDL.Text)],
+        [Result_1],
+        [],
+      ),
+    'total_supply' : IDL.Func([], [IDL.Nat64], ['query']),
+    'transfer_edition' : IDL.Func([IDL.Nat64, IDL.Principal], [Result_2], []),
+    'treasury_balance' : IDL.Func([], [IDL.Nat64], ['query']),
+    'unbookmark_vibe' : IDL.Func([IDL.Text], [Result_1], []),
+    'unfollow' : IDL.Func([IDL.Principal], [Result_2], []),
+    'unreact_vibe' : IDL.Func([IDL.Text, Reaction], [Result_1], []),
+    'update_collection' : IDL.Func(
+        [IDL.Nat64, IDL.Text, IDL.Text, IDL.Opt(IDL.Text)],
+        [Result_2],
+        [],
+      ),
+    'update_profile' : IDL.Func([ProfileUpdate], [Result_19], []),
+    'warn_creator' : IDL.Func([IDL.Text, IDL.Text], [Result_2], []),
   });
 };
-export const init = ({ IDL }) => { return []; };
+export const init = ({ IDL }) => {
+  const InitArgs = IDL.Record({ 'admins' : IDL.Vec(IDL.Principal) });
+  return [IDL.Opt(InitArgs)];
+};
@@ -286,7 +286,11 @@ function App() {
         throw new Error(generatedVibe);
       }
       // Mint the vibe on the blockchain
-      const vibeId = await backend.mint_vibe(generatedVibe);
+      const result = await backend.mint_vibe(generatedVibe, []);
+      if ('Err' in result) {
+        throw new Error(Object.keys(result.Err)[0]);
+      }
+      const vibeId = result.Ok;
       console.log("Minted vibe ID:", vibeId);
 
       setVibeInput('');
@@ -314,8 +318,11 @@ function App() {
 
     try {
       // Ensure we pass the original ID type to backend
-      const newLikes = await backend.like_vibe(vibeId);
-      const likesNum = bigIntToNumber(newLikes);
+      const result = await backend.like_vibe(vibeId);
+      if ('Err' in result) {
+        throw new Error(Object.keys(result.Err)[0]);
+      }
+      const likesNum = bigIntToNumber(result.Ok);
 
       // Update local state using string ID
       setVibes(prev => prev.map(v =>
@@ -355,8 +362,11 @@ function App() {
 
     try {
       // Ensure we pass the original ID type to backend
-      const newShares = await backend.share_vibe(vibeId);
-      const sharesNum = bigIntToNumber(newShares);
+      const result = await backend.share_vibe(vibeId);
+      if ('Err' in result) {
+        throw new Error(Object.keys(result.Err)[0]);
+      }
+      const sharesNum = bigIntToNumber(result.Ok);
 
       // Update local state using string ID
       setVibes(prev => prev.map(v =>
@@ -409,8 +419,10 @@ function App() {
       setIsLoading(true);
       try {
         await backend.reset_account();
+        // The treasury only refills a spent balance once a day
+        const newBalance = await backend.get_my_balance();
         setVibes([]);
-        setBalance(INITIAL_BALANCE);
+        setBalance(bigIntToNumber(newBalance));
         setReputation(1.0);
         setUserEngagements({ liked: new Set(), shared: new Set() });
 